@@ -1,11 +1,12 @@
 use cosmwasm_schema::write_api;
 
-use call_factory::*;
+use call_factory::{msg::MigrateMsg, *};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
-        query: QueryMsg
+        query: QueryMsg,
+        migrate: MigrateMsg
     }
 }
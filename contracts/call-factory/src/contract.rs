@@ -4,13 +4,20 @@ use cosmwasm_std::{
 };
 use cw_utils::parse_reply_instantiate_data;
 
-use common::errors::ContractError;
+use common::{
+    errors::ContractError,
+    events::{CallInstrumentCreated, PutInstrumentCreated},
+    migrate::set_migrated_version,
+};
+use protocol::helpers::assert_not_paused;
+use semver::Version;
 
 use crate::{
-    execute::{self, INSTANTIATE_CALL_INSTRUMENT_ID},
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    execute::{self, INSTANTIATE_CALL_INSTRUMENT_ID, INSTANTIATE_PUT_INSTRUMENT_ID},
+    migrations,
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     query,
-    state::{Config, CALL_INSTRUMENTS, TMP_INSTRUMENT},
+    state::{Config, CALL_INSTRUMENTS, PUT_INSTRUMENTS, TMP_INSTRUMENT},
 };
 
 const CONTRACT_NAME: &str = "crates.io:interchainnft-options-call-factory";
@@ -50,10 +57,61 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     let config = Config::load(deps.storage)?;
 
+    // the owner can still update the settings while the protocol is paused
+    if !matches!(msg, ExecuteMsg::UpdateConfig { .. }) {
+        assert_not_paused(&deps.querier, &config.protocol_addr)?;
+    }
+
     match msg {
-        ExecuteMsg::MakeCallInstrument { nft_addr } => {
-            execute::make_call_instrument(deps, &info.sender, nft_addr, &config)
+        ExecuteMsg::MakeCallInstrument {
+            nft_addr,
+            royalties_enabled,
+            auction_mode,
+            anti_snipe,
+            expiry_series,
+            keeper_bounty,
+        } => execute::make_call_instrument(
+            deps,
+            &info.sender,
+            nft_addr,
+            royalties_enabled.unwrap_or_default(),
+            auction_mode.unwrap_or_default(),
+            anti_snipe,
+            expiry_series,
+            keeper_bounty.unwrap_or_default(),
+            &config,
+        ),
+
+        ExecuteMsg::MakePutInstrument { nft_addr } => {
+            execute::make_put_instrument(deps, &info.sender, nft_addr, &config)
         }
+
+        ExecuteMsg::UpdateConfig {
+            call_code_id,
+            nft_symbol,
+            nft_name,
+            default_expiry_range,
+            default_allowed_denom,
+            default_min_bid_increment_bps,
+            default_settlement_auction_start_offset,
+            put_code_id,
+            put_nft_symbol,
+            put_nft_name,
+        } => execute::update_config(
+            deps,
+            &info.sender,
+            call_code_id,
+            nft_symbol,
+            nft_name,
+            default_expiry_range,
+            default_allowed_denom,
+            default_min_bid_increment_bps,
+            default_settlement_auction_start_offset,
+            put_code_id,
+            put_nft_symbol,
+            put_nft_name,
+            config,
+        ),
     }
 }
 
@@ -63,6 +121,10 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
     match msg {
         QueryMsg::GetCallInstrument { nft_addr } => query::get_call_instrument(deps, &nft_addr),
+
+        QueryMsg::GetPutInstrument { nft_addr } => query::get_put_instrument(deps, &nft_addr),
+
+        QueryMsg::Config {} => query::config(deps),
     }
 }
 
@@ -83,9 +145,53 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
                 &Addr::unchecked(&res.contract_address),
             )?;
             Ok(Response::new()
+                .add_event(
+                    CallInstrumentCreated {
+                        nft_addr: tmp.nft_addr,
+                        instrument_addr: Addr::unchecked(&res.contract_address),
+                    }
+                    .into(),
+                )
                 .add_attribute("call_instrument_addr", res.contract_address.to_string()))
         }
 
+        INSTANTIATE_PUT_INSTRUMENT_ID => {
+            let tmp = TMP_INSTRUMENT.load(deps.storage)?;
+            ensure!(
+                !PUT_INSTRUMENTS.has(deps.storage, &tmp.nft_addr),
+                StdError::generic_err("make_put_instrument - instrument already exist")
+            );
+            let res = parse_reply_instantiate_data(msg)?;
+            PUT_INSTRUMENTS.save(
+                deps.storage,
+                &tmp.nft_addr,
+                &Addr::unchecked(&res.contract_address),
+            )?;
+            Ok(Response::new()
+                .add_event(
+                    PutInstrumentCreated {
+                        nft_addr: tmp.nft_addr,
+                        instrument_addr: Addr::unchecked(&res.contract_address),
+                    }
+                    .into(),
+                )
+                .add_attribute("put_instrument_addr", res.contract_address.to_string()))
+        }
+
         _ => Err(ContractError::UnknownReplyID {}),
     }
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = set_migrated_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if previous < Version::new(0, 2, 0) {
+        migrations::migrate_from_v0_1(deps.storage, msg)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", previous.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
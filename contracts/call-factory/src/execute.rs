@@ -1,32 +1,55 @@
-use cosmwasm_std::{ensure, Addr, DepsMut, Response, StdError, SubMsg};
+use cosmwasm_std::{ensure, Addr, DepsMut, Response, StdError, SubMsg, Uint128};
 
-use call::utils::call_instrument_instantiate_wasm_msg;
-use common::errors::ContractError;
+use call::{
+    msg::{AntiSnipe, AuctionMode},
+    utils::call_instrument_instantiate_wasm_msg,
+};
+use common::{
+    denom::Denom,
+    errors::ContractError,
+    types::{ExpiryRange, ExpirySeries},
+};
+use put::utils::put_instrument_instantiate_wasm_msg;
 
-use crate::state::{Config, TmpInstrumentInfo, CALL_INSTRUMENTS, TMP_INSTRUMENT};
+use crate::state::{Config, TmpInstrumentInfo, CALL_INSTRUMENTS, PUT_INSTRUMENTS, TMP_INSTRUMENT};
 
 /// A `reply` call code ID used in a sub-message.
 pub(crate) const INSTANTIATE_CALL_INSTRUMENT_ID: u64 = 10;
+pub(crate) const INSTANTIATE_PUT_INSTRUMENT_ID: u64 = 11;
 
 /// Create a call option instrument for a specific underlying asset address
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn make_call_instrument(
     deps: DepsMut,
     sender: &Addr,
     nft_addr: String,
+    royalties_enabled: bool,
+    auction_mode: AuctionMode,
+    anti_snipe: Option<AntiSnipe>,
+    expiry_series: Option<ExpirySeries>,
+    keeper_bounty: Uint128,
     config: &Config,
 ) -> Result<Response, ContractError> {
     cw_ownable::assert_owner(deps.as_ref().storage, sender)?;
 
     let call_instrument_instantiate_wasm_msg = call_instrument_instantiate_wasm_msg(
-        config.nft_name.clone(),
-        config.nft_symbol.clone(),
         config.call_code_id,
-        nft_addr.clone(),
-        config.protocol_addr.to_string(),
-        config.vault_factory_addr.to_string(),
-        config.default_minimum_option_duration,
-        config.default_allowed_denom.to_string(),
-        config.default_min_bid_increment_bps,
+        call::InstantiateMsg {
+            name: config.nft_name.clone(),
+            symbol: config.nft_symbol.clone(),
+            protocol_addr: config.protocol_addr.to_string(),
+            allowed_underlying_nft: nft_addr.clone(),
+            vault_factory_addr: config.vault_factory_addr.to_string(),
+            expiry_range: config.default_expiry_range.clone(),
+            expiry_series,
+            allowed_denom: config.default_allowed_denom.clone(),
+            min_bid_increment_bps: config.default_min_bid_increment_bps,
+            settlement_auction_start_offset: config.default_settlement_auction_start_offset,
+            royalties_enabled,
+            auction_mode,
+            anti_snipe,
+            keeper_bounty,
+        },
         "Interchainnft-options call option nft".into(),
     )?;
 
@@ -47,3 +70,83 @@ pub(crate) fn make_call_instrument(
         .add_attribute("action", "make_call_instrument")
         .add_attribute("sender", sender))
 }
+
+/// Create a put option instrument for a specific underlying asset address
+pub(crate) fn make_put_instrument(
+    deps: DepsMut,
+    sender: &Addr,
+    nft_addr: String,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.as_ref().storage, sender)?;
+
+    let put_instrument_instantiate_wasm_msg = put_instrument_instantiate_wasm_msg(
+        config.put_code_id,
+        put::InstantiateMsg {
+            name: config.put_nft_name.clone(),
+            symbol: config.put_nft_symbol.clone(),
+            protocol_addr: config.protocol_addr.to_string(),
+            allowed_underlying_nft: nft_addr.clone(),
+            vault_factory_addr: config.vault_factory_addr.to_string(),
+            minimum_option_duration: config.default_expiry_range.min,
+            allowed_denom: config.default_allowed_denom.clone(),
+        },
+        "Interchainnft-options put option nft".into(),
+    )?;
+
+    let nft_addr = deps.api.addr_validate(&nft_addr)?;
+
+    ensure!(
+        !PUT_INSTRUMENTS.has(deps.storage, &nft_addr),
+        StdError::generic_err("make_put_instrument - instrument already exist")
+    );
+
+    TMP_INSTRUMENT.save(deps.storage, &TmpInstrumentInfo { nft_addr })?;
+
+    Ok(Response::default()
+        .add_submessage(SubMsg::reply_on_success(
+            put_instrument_instantiate_wasm_msg,
+            INSTANTIATE_PUT_INSTRUMENT_ID,
+        ))
+        .add_attribute("action", "make_put_instrument")
+        .add_attribute("sender", sender))
+}
+
+/// Updates the settings used for new instruments
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_config(
+    deps: DepsMut,
+    sender: &Addr,
+    call_code_id: Option<u64>,
+    nft_symbol: Option<String>,
+    nft_name: Option<String>,
+    default_expiry_range: Option<ExpiryRange>,
+    default_allowed_denom: Option<Denom>,
+    default_min_bid_increment_bps: Option<u64>,
+    default_settlement_auction_start_offset: Option<u64>,
+    put_code_id: Option<u64>,
+    put_nft_symbol: Option<String>,
+    put_nft_name: Option<String>,
+    mut config: Config,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.as_ref().storage, sender)?;
+
+    config.call_code_id = call_code_id.unwrap_or(config.call_code_id);
+    config.nft_symbol = nft_symbol.unwrap_or(config.nft_symbol);
+    config.nft_name = nft_name.unwrap_or(config.nft_name);
+    config.default_expiry_range = default_expiry_range.unwrap_or(config.default_expiry_range);
+    config.default_allowed_denom = default_allowed_denom.unwrap_or(config.default_allowed_denom);
+    config.default_min_bid_increment_bps =
+        default_min_bid_increment_bps.unwrap_or(config.default_min_bid_increment_bps);
+    config.default_settlement_auction_start_offset = default_settlement_auction_start_offset
+        .unwrap_or(config.default_settlement_auction_start_offset);
+    config.put_code_id = put_code_id.unwrap_or(config.put_code_id);
+    config.put_nft_symbol = put_nft_symbol.unwrap_or(config.put_nft_symbol);
+    config.put_nft_name = put_nft_name.unwrap_or(config.put_nft_name);
+    config.validate(deps.api)?;
+    config.save(deps.storage)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", sender))
+}
@@ -1,5 +1,6 @@
 pub mod contract;
 pub mod execute;
+pub mod migrations;
 pub mod msg;
 pub mod query;
 pub mod state;
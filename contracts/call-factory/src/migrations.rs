@@ -0,0 +1,71 @@
+//! Storage migrations of the call factory, run by the `migrate` entry point.
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Storage};
+use cw_storage_plus::Item;
+
+use common::{denom::Denom, errors::ContractError, types::ExpiryRange};
+
+use crate::{msg::MigrateMsg, state::Config};
+
+/// Storage layout of version 0.1, before put instruments and settlement auction offsets were added.
+mod v0_1 {
+    use super::*;
+
+    #[cw_serde]
+    pub struct Config {
+        pub protocol_addr: Addr,
+        pub call_code_id: u64,
+        pub nft_symbol: String,
+        pub nft_name: String,
+        pub default_minimum_option_duration: u64,
+        pub default_allowed_denom: String,
+        pub default_min_bid_increment_bps: u64,
+        pub vault_factory_addr: String,
+    }
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+}
+
+/// Migrates the storage of version 0.1, the settings added since then must be set in the migrate message.
+pub(crate) fn migrate_from_v0_1(
+    storage: &mut dyn Storage,
+    msg: MigrateMsg,
+) -> Result<(), ContractError> {
+    let legacy = v0_1::CONFIG.load(storage)?;
+    let (
+        Some(default_settlement_auction_start_offset),
+        Some(default_maximum_option_duration),
+        Some(put_code_id),
+        Some(put_nft_symbol),
+        Some(put_nft_name),
+    ) = (
+        msg.default_settlement_auction_start_offset,
+        msg.default_maximum_option_duration,
+        msg.put_code_id,
+        msg.put_nft_symbol,
+        msg.put_nft_name,
+    )
+    else {
+        return Err(ContractError::InvalidConfig {});
+    };
+
+    Config {
+        protocol_addr: legacy.protocol_addr,
+        call_code_id: legacy.call_code_id,
+        nft_symbol: legacy.nft_symbol,
+        nft_name: legacy.nft_name,
+        default_expiry_range: ExpiryRange {
+            min: legacy.default_minimum_option_duration,
+            max: default_maximum_option_duration,
+        },
+        default_allowed_denom: Denom::from(legacy.default_allowed_denom),
+        default_min_bid_increment_bps: legacy.default_min_bid_increment_bps,
+        default_settlement_auction_start_offset,
+        vault_factory_addr: legacy.vault_factory_addr,
+        put_code_id,
+        put_nft_symbol,
+        put_nft_name,
+    }
+    .save(storage)?;
+    Ok(())
+}
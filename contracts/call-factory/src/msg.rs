@@ -1,5 +1,13 @@
+use call::msg::{AntiSnipe, AuctionMode};
+use common::{
+    denom::Denom,
+    types::{ExpiryRange, ExpirySeries},
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Uint128};
+
+#[allow(unused_imports)]
+use crate::state::Config;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -13,20 +21,74 @@ pub struct InstantiateMsg {
     pub nft_symbol: String,
     /// Name of call option nft
     pub nft_name: String,
-    /// Default min duration of an created option
-    pub default_minimum_option_duration: u64,
-    /// TODO
-    pub default_allowed_denom: String,
+    /// Default bounds in seconds of the duration of created options
+    pub default_expiry_range: ExpiryRange,
+    /// Native denom or cw20 token used by created instruments
+    pub default_allowed_denom: Denom,
     /// TODO
     pub default_min_bid_increment_bps: u64,
+    /// Default amount of time in seconds before the expiration of the option that the settlement auction will begin,
+    /// shorter than the default minimum option duration
+    pub default_settlement_auction_start_offset: u64,
     /// The address of the vault factory
     pub vault_factory_addr: String,
+    /// Put option contract code identifier
+    pub put_code_id: u64,
+    /// Symbol of put option nft
+    pub put_nft_symbol: String,
+    /// Name of put option nft
+    pub put_nft_name: String,
+}
+
+/// The settings added since 0.1, required when migrating from that version.
+#[cw_serde]
+pub struct MigrateMsg {
+    /// Default amount of time in seconds before the expiration of the option that the settlement auction will begin
+    pub default_settlement_auction_start_offset: Option<u64>,
+    /// Default maximum duration in seconds of created options
+    pub default_maximum_option_duration: Option<u64>,
+    /// Put option contract code identifier
+    pub put_code_id: Option<u64>,
+    /// Symbol of put option nft
+    pub put_nft_symbol: Option<String>,
+    /// Name of put option nft
+    pub put_nft_name: Option<String>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    ///  Create a call option instrument for a specific underlying asset address
-    MakeCallInstrument { nft_addr: String },
+    ///  Create a call option instrument for a specific underlying asset address.
+    /// If `royalties_enabled` is set, cw2981 royalties of the collection are paid on settlement.
+    /// The settlement auctions are open unless a sealed bid `auction_mode` is set,
+    /// `anti_snipe` extends open auctions on late bids.
+    /// Options can expire at any time within the default expiry range unless an `expiry_series` is set.
+    /// Keepers settling options are paid the `keeper_bounty`, none if not set.
+    MakeCallInstrument {
+        nft_addr: String,
+        royalties_enabled: Option<bool>,
+        auction_mode: Option<AuctionMode>,
+        anti_snipe: Option<AntiSnipe>,
+        expiry_series: Option<ExpirySeries>,
+        keeper_bounty: Option<Uint128>,
+    },
+
+    /// Create a put option instrument for a specific underlying asset address
+    MakePutInstrument { nft_addr: String },
+
+    /// Allows the owner to update the settings used for new instruments.
+    /// Instruments that are already deployed are not changed.
+    UpdateConfig {
+        call_code_id: Option<u64>,
+        nft_symbol: Option<String>,
+        nft_name: Option<String>,
+        default_expiry_range: Option<ExpiryRange>,
+        default_allowed_denom: Option<Denom>,
+        default_min_bid_increment_bps: Option<u64>,
+        default_settlement_auction_start_offset: Option<u64>,
+        put_code_id: Option<u64>,
+        put_nft_symbol: Option<String>,
+        put_nft_name: Option<String>,
+    },
 }
 
 #[cw_serde]
@@ -35,4 +97,12 @@ pub enum QueryMsg {
     /// Lookup the call instrument contract based on the asset address
     #[returns(Option<Addr>)]
     GetCallInstrument { nft_addr: String },
+
+    /// Lookup the put instrument contract based on the asset address
+    #[returns(Option<Addr>)]
+    GetPutInstrument { nft_addr: String },
+
+    /// Gets the settings used for new instruments.
+    #[returns(Config)]
+    Config {},
 }
@@ -1,6 +1,6 @@
 use cosmwasm_std::{to_binary, Binary, Deps, StdResult};
 
-use crate::state::CALL_INSTRUMENTS;
+use crate::state::{Config, CALL_INSTRUMENTS, PUT_INSTRUMENTS};
 
 /// Lookup the call instrument contract based on the asset address
 pub(crate) fn get_call_instrument(deps: Deps, nft_addr: &str) -> StdResult<Binary> {
@@ -8,3 +8,15 @@ pub(crate) fn get_call_instrument(deps: Deps, nft_addr: &str) -> StdResult<Binar
     let call_instrument_addr = CALL_INSTRUMENTS.may_load(deps.storage, &nft_addr)?;
     to_binary(&call_instrument_addr)
 }
+
+/// Lookup the put instrument contract based on the asset address
+pub(crate) fn get_put_instrument(deps: Deps, nft_addr: &str) -> StdResult<Binary> {
+    let nft_addr = deps.api.addr_validate(nft_addr)?;
+    let put_instrument_addr = PUT_INSTRUMENTS.may_load(deps.storage, &nft_addr)?;
+    to_binary(&put_instrument_addr)
+}
+
+/// Gets the settings used for new instruments
+pub(crate) fn config(deps: Deps) -> StdResult<Binary> {
+    to_binary(&Config::load(deps.storage)?)
+}
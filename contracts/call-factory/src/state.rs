@@ -1,6 +1,6 @@
-use common::errors::ContractError;
+use common::{denom::Denom, errors::ContractError, types::ExpiryRange};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Api, StdResult, Storage};
+use cosmwasm_std::{ensure, Addr, Api, StdResult, Storage};
 use cw_storage_plus::{Item, Map};
 
 use macros::ConfigStorage;
@@ -21,19 +21,36 @@ pub struct Config {
     pub nft_symbol: String,
     /// Name of call option nft
     pub nft_name: String,
-    /// Default min duration of an created option
-    pub default_minimum_option_duration: u64,
-    /// TODO
-    pub default_allowed_denom: String,
+    /// Default bounds in seconds of the duration of created options
+    pub default_expiry_range: ExpiryRange,
+    /// Native denom or cw20 token used by created instruments
+    pub default_allowed_denom: Denom,
     /// TODO
     pub default_min_bid_increment_bps: u64,
+    /// Default amount of time in seconds before the expiration of the option that the settlement auction will begin
+    pub default_settlement_auction_start_offset: u64,
     /// The address of the vault factory
     pub vault_factory_addr: String,
+    /// Put option contract code identifier
+    pub put_code_id: u64,
+    /// Symbol of put option nft
+    pub put_nft_symbol: String,
+    /// Name of put option nft
+    pub put_nft_name: String,
 }
 
 impl Config {
     pub fn validate(&self, api: &dyn Api) -> Result<(), ContractError> {
         api.addr_validate(self.protocol_addr.as_str())?;
+        self.default_allowed_denom.validate(api)?;
+        self.default_expiry_range.validate()?;
+        // the settlement auction of an option can't open before the option is written
+        ensure!(
+            self.default_settlement_auction_start_offset < self.default_expiry_range.min,
+            ContractError::AuctionStartOffsetTooLong {
+                min: self.default_expiry_range.min
+            }
+        );
         Ok(())
     }
 }
@@ -43,12 +60,16 @@ impl From<InstantiateMsg> for Config {
         Config {
             protocol_addr: Addr::unchecked(value.protocol_addr),
             call_code_id: value.call_code_id,
-            default_minimum_option_duration: value.default_minimum_option_duration,
+            default_expiry_range: value.default_expiry_range,
             default_allowed_denom: value.default_allowed_denom,
             default_min_bid_increment_bps: value.default_min_bid_increment_bps,
+            default_settlement_auction_start_offset: value.default_settlement_auction_start_offset,
             nft_symbol: value.nft_symbol,
             nft_name: value.nft_name,
             vault_factory_addr: value.vault_factory_addr,
+            put_code_id: value.put_code_id,
+            put_nft_symbol: value.put_nft_symbol,
+            put_nft_name: value.put_nft_name,
         }
     }
 }
@@ -56,6 +77,9 @@ impl From<InstantiateMsg> for Config {
 /// Registry of all of the active markets projects with supported call instruments
 pub(crate) const CALL_INSTRUMENTS: Map<&Addr, Addr> = Map::new("call_instrument");
 
+/// Registry of all of the active markets projects with supported put instruments
+pub(crate) const PUT_INSTRUMENTS: Map<&Addr, Addr> = Map::new("put_instrument");
+
 /// This is an intermediate structure for storing a vault info. It is used in a submessage response.
 #[cw_serde]
 pub struct TmpInstrumentInfo {
@@ -1,9 +1,14 @@
 use std::sync::Mutex;
 
-use common::errors::ContractError;
+use common::{
+    denom::Denom,
+    errors::ContractError,
+    types::{ExpiryRange, ExpirySeries},
+};
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
-    to_binary, Addr, CosmosMsg, MessageInfo, OwnedDeps, ReplyOn, Response, SubMsg, WasmMsg,
+    to_binary, Addr, ContractResult, CosmosMsg, MessageInfo, OwnedDeps, ReplyOn, Response, SubMsg,
+    SystemResult, Uint128, WasmMsg,
 };
 use cw2::ContractVersion;
 use cw_ownable::OwnershipError;
@@ -14,7 +19,7 @@ use log::debug;
 
 use crate::{
     contract::{self, *},
-    execute::INSTANTIATE_CALL_INSTRUMENT_ID,
+    execute::{INSTANTIATE_CALL_INSTRUMENT_ID, INSTANTIATE_PUT_INSTRUMENT_ID},
     msg::*,
     state::*,
 };
@@ -27,6 +32,13 @@ static SETUP_LOGGER: Lazy<Mutex<()>> = Lazy::new(|| {
 const OWNER: &str = "owner_addr";
 const USER: &str = "user_addr";
 const CALL_INSTRUMENT_CODE_ID: u64 = 11;
+const PUT_INSTRUMENT_CODE_ID: u64 = 12;
+
+/// Mocks the protocol contract, which answers whether the protocol is paused.
+fn mock_protocol(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, is_paused: bool) {
+    deps.querier
+        .update_wasm(move |_| SystemResult::Ok(ContractResult::Ok(to_binary(&is_paused).unwrap())));
+}
 
 #[allow(dead_code)]
 #[allow(let_underscore_lock)]
@@ -34,6 +46,7 @@ fn setup(info_sender: &str) -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, Me
     let _ = SETUP_LOGGER.lock().unwrap();
 
     let mut deps = mock_dependencies();
+    mock_protocol(&mut deps, false);
     let info = mock_info(info_sender, &[]);
 
     let msg = InstantiateMsg {
@@ -42,10 +55,17 @@ fn setup(info_sender: &str) -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, Me
         owner: Some(OWNER.to_string()),
         nft_symbol: "nft_symbol".to_string(),
         nft_name: "nft_name".to_string(),
-        default_minimum_option_duration: 1,
-        default_allowed_denom: "ATOM".to_string(),
+        default_expiry_range: ExpiryRange {
+            min: 3600,
+            max: 365 * 86400,
+        },
+        default_allowed_denom: Denom::from("ATOM"),
         default_min_bid_increment_bps: 100,
+        default_settlement_auction_start_offset: 1800,
         vault_factory_addr: "vault_factory_addr".to_string(),
+        put_code_id: PUT_INSTRUMENT_CODE_ID,
+        put_nft_symbol: "put_nft_symbol".to_string(),
+        put_nft_name: "put_nft_name".to_string(),
     };
     let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg);
 
@@ -62,10 +82,17 @@ fn proper_initialization() {
         owner: Some(OWNER.to_string()),
         nft_symbol: "nft_symbol".to_string(),
         nft_name: "nft_name".to_string(),
-        default_minimum_option_duration: 1,
-        default_allowed_denom: "ATOM".to_string(),
+        default_expiry_range: ExpiryRange {
+            min: 3600,
+            max: 365 * 86400,
+        },
+        default_allowed_denom: Denom::from("ATOM"),
         default_min_bid_increment_bps: 100,
+        default_settlement_auction_start_offset: 1800,
         vault_factory_addr: "vault_factory_addr".to_string(),
+        put_code_id: PUT_INSTRUMENT_CODE_ID,
+        put_nft_symbol: "put_nft_symbol".to_string(),
+        put_nft_name: "put_nft_name".to_string(),
     };
 
     let mut deps = mock_dependencies();
@@ -85,10 +112,20 @@ fn proper_initialization() {
     assert_eq!(config.call_code_id, CALL_INSTRUMENT_CODE_ID);
     assert_eq!(config.nft_symbol, "nft_symbol");
     assert_eq!(config.nft_name, "nft_name");
-    assert_eq!(config.default_minimum_option_duration, 1);
-    assert_eq!(config.default_allowed_denom, "ATOM");
+    assert_eq!(
+        config.default_expiry_range,
+        ExpiryRange {
+            min: 3600,
+            max: 365 * 86400
+        }
+    );
+    assert_eq!(config.default_allowed_denom, Denom::from("ATOM"));
     assert_eq!(config.default_min_bid_increment_bps, 100);
+    assert_eq!(config.default_settlement_auction_start_offset, 1800);
     assert_eq!(config.vault_factory_addr, "vault_factory_addr");
+    assert_eq!(config.put_code_id, PUT_INSTRUMENT_CODE_ID);
+    assert_eq!(config.put_nft_symbol, "put_nft_symbol");
+    assert_eq!(config.put_nft_name, "put_nft_name");
 
     let version = cw2::get_contract_version(&deps.storage).unwrap();
     assert_eq!(
@@ -112,6 +149,14 @@ fn make_call_instrument() {
 
     let msg = ExecuteMsg::MakeCallInstrument {
         nft_addr: "call_instrument_addr".to_string(),
+        royalties_enabled: Some(true),
+        auction_mode: None,
+        anti_snipe: None,
+        expiry_series: Some(ExpirySeries::Weekly {
+            weekday: 4,
+            hour: 8,
+        }),
+        keeper_bounty: Some(Uint128::new(1_000)),
     };
 
     let resp = contract::execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -122,17 +167,29 @@ fn make_call_instrument() {
             .add_submessage(SubMsg {
                 id: INSTANTIATE_CALL_INSTRUMENT_ID,
                 msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
-                    admin: None,
+                    admin: Some("protocol_addr".to_string()),
                     code_id: CALL_INSTRUMENT_CODE_ID,
                     msg: to_binary(&call::msg::InstantiateMsg {
                         name: "nft_name".to_string(),
                         symbol: "nft_symbol".to_string(),
                         protocol_addr: "protocol_addr".to_string(),
                         allowed_underlying_nft: "call_instrument_addr".to_string(),
-                        minimum_option_duration: 1,
-                        allowed_denom: "ATOM".to_string(),
+                        expiry_range: ExpiryRange {
+                            min: 3600,
+                            max: 365 * 86400
+                        },
+                        expiry_series: Some(ExpirySeries::Weekly {
+                            weekday: 4,
+                            hour: 8,
+                        }),
+                        allowed_denom: Denom::from("ATOM"),
                         min_bid_increment_bps: 100,
+                        settlement_auction_start_offset: 1800,
                         vault_factory_addr: "vault_factory_addr".to_string(),
+                        royalties_enabled: true,
+                        auction_mode: call::msg::AuctionMode::Open,
+                        anti_snipe: None,
+                        keeper_bounty: Uint128::new(1_000),
                     })
                     .unwrap(),
                     funds: vec![],
@@ -146,12 +203,56 @@ fn make_call_instrument() {
     );
 }
 
+#[test]
+fn make_put_instrument() {
+    let (mut deps, info) = setup(OWNER);
+
+    let msg = ExecuteMsg::MakePutInstrument {
+        nft_addr: "nft_addr".to_string(),
+    };
+
+    let resp = contract::execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    assert_eq!(
+        resp,
+        Response::new()
+            .add_submessage(SubMsg {
+                id: INSTANTIATE_PUT_INSTRUMENT_ID,
+                msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
+                    admin: Some("protocol_addr".to_string()),
+                    code_id: PUT_INSTRUMENT_CODE_ID,
+                    msg: to_binary(&put::msg::InstantiateMsg {
+                        name: "put_nft_name".to_string(),
+                        symbol: "put_nft_symbol".to_string(),
+                        protocol_addr: "protocol_addr".to_string(),
+                        allowed_underlying_nft: "nft_addr".to_string(),
+                        vault_factory_addr: "vault_factory_addr".to_string(),
+                        minimum_option_duration: 3600,
+                        allowed_denom: Denom::from("ATOM"),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                    label: "Interchainnft-options put option nft".into(),
+                }),
+                reply_on: ReplyOn::Success,
+                gas_limit: None,
+            })
+            .add_attribute("action", "make_put_instrument")
+            .add_attribute("sender", OWNER)
+    );
+}
+
 #[test]
 fn not_owner_cant_make_call_instrument() {
     let (mut deps, info) = setup(USER);
 
     let msg = ExecuteMsg::MakeCallInstrument {
         nft_addr: "nft_addr".to_string(),
+        royalties_enabled: None,
+        auction_mode: None,
+        anti_snipe: None,
+        expiry_series: None,
+        keeper_bounty: None,
     };
 
     let resp = contract::execute(deps.as_mut(), mock_env(), info, msg);
@@ -161,5 +262,59 @@ fn not_owner_cant_make_call_instrument() {
     );
 }
 
+#[test]
+fn owner_updates_config_while_paused() {
+    let (mut deps, info) = setup(OWNER);
+    mock_protocol(&mut deps, true);
+
+    let update = |default_allowed_denom: Option<Denom>| ExecuteMsg::UpdateConfig {
+        call_code_id: Some(21),
+        nft_symbol: None,
+        nft_name: None,
+        default_expiry_range: Some(ExpiryRange {
+            min: 3600,
+            max: 30 * 86400,
+        }),
+        default_allowed_denom,
+        default_min_bid_increment_bps: None,
+        default_settlement_auction_start_offset: None,
+        put_code_id: None,
+        put_nft_symbol: None,
+        put_nft_name: None,
+    };
+
+    let resp = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        update(None),
+    );
+    assert_eq!(
+        resp,
+        Err(ContractError::OwnershipError(OwnershipError::NotOwner))
+    );
+
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        update(Some(Denom::from("uosmo"))),
+    )
+    .unwrap();
+
+    let config = Config::load(&deps.storage).unwrap();
+    assert_eq!(config.call_code_id, 21);
+    assert_eq!(
+        config.default_expiry_range,
+        ExpiryRange {
+            min: 3600,
+            max: 30 * 86400
+        }
+    );
+    assert_eq!(config.default_allowed_denom, Denom::from("uosmo"));
+    assert_eq!(config.default_min_bid_increment_bps, 100);
+    assert_eq!(config.nft_name, "nft_name");
+}
+
 // TODO integration test create vault if already exists
 // TODO integration test get created vault
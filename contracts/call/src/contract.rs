@@ -1,18 +1,29 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult};
+use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdError};
 
-use common::errors::ContractError;
+use common::{errors::ContractError, migrate::set_migrated_version};
+use protocol::helpers::assert_not_paused;
+use semver::Version;
 
 use crate::{
-    execute,
-    msg::{CallInstrumentExecuteMsg, CallInstrumentQueryMsg, InstantiateMsg},
+    execute::{self, REFUND_REPLY_ID},
+    migrations,
+    msg::{
+        CallInstrumentExecuteMsg, CallInstrumentQueryMsg, InstantiateMsg, MigrateMsg,
+        OptionMetadata,
+    },
     query,
     state::Config,
 };
 
-pub type CallInstrumentContract<'a> =
-    cw721_base::Cw721Contract<'a, Empty, Empty, CallInstrumentExecuteMsg, CallInstrumentQueryMsg>;
+pub type CallInstrumentContract<'a> = cw721_base::Cw721Contract<
+    'a,
+    OptionMetadata,
+    Empty,
+    CallInstrumentExecuteMsg,
+    CallInstrumentQueryMsg,
+>;
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:interchainnft-options-call-instrument";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -50,10 +61,55 @@ pub fn execute(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    msg: cw721_base::ExecuteMsg<Empty, CallInstrumentExecuteMsg>,
+    msg: cw721_base::ExecuteMsg<OptionMetadata, CallInstrumentExecuteMsg>,
 ) -> Result<Response, ContractError> {
     let config = Config::load(deps.storage)?;
 
+    // new options and bids are not accepted while the protocol is paused,
+    // exit paths stay available so users are never trapped. cw20 messages are checked once parsed
+    if matches!(
+        msg,
+        cw721_base::ExecuteMsg::Extension {
+            msg: CallInstrumentExecuteMsg::MintWithNFT { .. }
+                | CallInstrumentExecuteMsg::MintWithVault { .. }
+                | CallInstrumentExecuteMsg::MintWithEntitledVault { .. }
+                | CallInstrumentExecuteMsg::MintBatchWithNFT { .. }
+                | CallInstrumentExecuteMsg::MintBatchWithVault { .. }
+                | CallInstrumentExecuteMsg::AmendOption { .. }
+                | CallInstrumentExecuteMsg::RollOption { .. }
+                | CallInstrumentExecuteMsg::Bid { .. }
+                | CallInstrumentExecuteMsg::CommitBid { .. }
+                | CallInstrumentExecuteMsg::ListOption { .. }
+                | CallInstrumentExecuteMsg::BuyOption { .. }
+        }
+    ) {
+        assert_not_paused(&deps.querier, &config.protocol_addr)?;
+    }
+
+    // existing options are handled under the terms they were written under
+    let config = match &msg {
+        cw721_base::ExecuteMsg::Extension {
+            msg:
+                CallInstrumentExecuteMsg::Bid { option_id }
+                | CallInstrumentExecuteMsg::CommitBid { option_id, .. }
+                | CallInstrumentExecuteMsg::RevealBid { option_id, .. }
+                | CallInstrumentExecuteMsg::ReleaseDeposit { option_id, .. }
+                | CallInstrumentExecuteMsg::Exercise { option_id }
+                | CallInstrumentExecuteMsg::ReclaimAsset { option_id, .. }
+                | CallInstrumentExecuteMsg::AmendOption { option_id, .. }
+                | CallInstrumentExecuteMsg::RollOption { option_id, .. }
+                | CallInstrumentExecuteMsg::SettleOption { option_id }
+                | CallInstrumentExecuteMsg::BurnExpiredOption { option_id, .. }
+                | CallInstrumentExecuteMsg::FundBurnBounty { option_id }
+                | CallInstrumentExecuteMsg::ClaimOptionProceeds { option_id }
+                | CallInstrumentExecuteMsg::ListOption { option_id, .. }
+                | CallInstrumentExecuteMsg::CancelListing { option_id }
+                | CallInstrumentExecuteMsg::BuyOption { option_id }
+                | CallInstrumentExecuteMsg::FinalizeListing { option_id },
+        } => config.for_option(deps.storage, option_id)?,
+        _ => config,
+    };
+
     match msg {
         // handle custom call option messages
         cw721_base::ExecuteMsg::Extension { msg } => match msg {
@@ -62,8 +118,17 @@ pub fn execute(
                 nft_id,
                 strike,
                 expiration,
+                exercise_style,
             } => execute::mint_with_nft(
-                deps, &env, info, nft_addr, nft_id, strike, expiration, &config,
+                deps,
+                &env,
+                info,
+                nft_addr,
+                nft_id,
+                strike,
+                expiration,
+                exercise_style.unwrap_or_default(),
+                &config,
             ),
 
             CallInstrumentExecuteMsg::MintWithVault {
@@ -71,8 +136,17 @@ pub fn execute(
                 asset_id,
                 strike,
                 expiration,
+                exercise_style,
             } => execute::mint_with_vault(
-                deps, env, info, vault_addr, asset_id, strike, expiration, &config,
+                deps,
+                env,
+                info,
+                vault_addr,
+                asset_id,
+                strike,
+                expiration,
+                exercise_style.unwrap_or_default(),
+                &config,
             ),
 
             CallInstrumentExecuteMsg::MintWithEntitledVault {
@@ -80,6 +154,7 @@ pub fn execute(
                 asset_id,
                 strike,
                 expiration,
+                exercise_style,
             } => execute::mint_with_entitled_vault(
                 deps,
                 &env,
@@ -88,11 +163,63 @@ pub fn execute(
                 asset_id,
                 strike,
                 expiration,
+                exercise_style.unwrap_or_default(),
+                &config,
+            ),
+
+            CallInstrumentExecuteMsg::MintBatchWithNFT {
+                nft_addr,
+                entries,
+                exercise_style,
+            } => execute::mint_batch_with_nft(
+                deps,
+                &env,
+                info,
+                nft_addr,
+                entries,
+                exercise_style.unwrap_or_default(),
+                &config,
+            ),
+
+            CallInstrumentExecuteMsg::MintBatchWithVault {
+                vault_addr,
+                entries,
+                exercise_style,
+            } => execute::mint_batch_with_vault(
+                deps,
+                &env,
+                info,
+                vault_addr,
+                entries,
+                exercise_style.unwrap_or_default(),
                 &config,
             ),
 
             CallInstrumentExecuteMsg::Bid { option_id } => {
-                execute::bid(deps, info, &option_id, &config)
+                execute::bid(deps, &env, info, &option_id, &config)
+            }
+
+            CallInstrumentExecuteMsg::CommitBid {
+                option_id,
+                commitment,
+            } => execute::commit_bid(deps, &env, info, &option_id, commitment, &config),
+
+            CallInstrumentExecuteMsg::RevealBid {
+                option_id,
+                amount,
+                salt,
+            } => execute::reveal_bid(deps, &env, info, &option_id, amount, salt, &config),
+
+            CallInstrumentExecuteMsg::ReleaseDeposit { option_id, bidder } => {
+                execute::release_deposit(deps, &env, info, &option_id, bidder, &config)
+            }
+
+            CallInstrumentExecuteMsg::Exercise { option_id } => {
+                execute::exercise(deps, &env, info, &option_id, &config)
+            }
+
+            CallInstrumentExecuteMsg::Receive(msg) => {
+                execute::receive_cw20(deps, &env, info, msg, &config)
             }
 
             CallInstrumentExecuteMsg::ReclaimAsset {
@@ -100,18 +227,115 @@ pub fn execute(
                 withdraw,
             } => execute::reclaim_asset(deps, env, info, &option_id, withdraw, &config),
 
+            CallInstrumentExecuteMsg::AmendOption {
+                option_id,
+                new_strike,
+                new_expiration,
+            } => execute::amend_option(
+                deps,
+                &env,
+                info,
+                &option_id,
+                new_strike,
+                new_expiration,
+                &config,
+            ),
+
+            CallInstrumentExecuteMsg::RollOption {
+                option_id,
+                new_strike,
+                new_expiration,
+            } => execute::roll_option(
+                deps,
+                &env,
+                info,
+                &option_id,
+                new_strike,
+                new_expiration,
+                &config,
+            ),
+
             CallInstrumentExecuteMsg::SettleOption { option_id } => {
                 execute::settle_option(deps, env, info, &option_id, &config)
             }
 
-            CallInstrumentExecuteMsg::BurnExpiredOption { option_id } => {
-                execute::burn_expired_option(deps, env, info, &option_id)
+            CallInstrumentExecuteMsg::SettleBatch { option_ids } => {
+                execute::settle_batch(deps, env, info, option_ids, &config)
+            }
+
+            CallInstrumentExecuteMsg::BurnExpiredOption {
+                option_id,
+                withdraw,
+            } => execute::burn_expired_option(
+                deps,
+                env,
+                info,
+                &option_id,
+                withdraw.unwrap_or_default(),
+                &config,
+            ),
+
+            CallInstrumentExecuteMsg::FundBurnBounty { option_id } => {
+                execute::fund_burn_bounty(deps, info, &option_id, &config)
             }
 
             CallInstrumentExecuteMsg::ClaimOptionProceeds { option_id } => {
                 execute::claim_option_proceeds(deps, env, info, &option_id, &config)
             }
 
+            CallInstrumentExecuteMsg::WithdrawRefunds {} => execute::withdraw_refunds(deps, info),
+
+            CallInstrumentExecuteMsg::ListOption {
+                option_id,
+                premium,
+                expires,
+                kind,
+            } => execute::list_option(
+                deps,
+                &env,
+                info,
+                &option_id,
+                premium,
+                expires,
+                kind.unwrap_or_default(),
+                &config,
+            ),
+
+            CallInstrumentExecuteMsg::CancelListing { option_id } => {
+                execute::cancel_listing(deps, info, &option_id, &config)
+            }
+
+            CallInstrumentExecuteMsg::BuyOption { option_id } => {
+                execute::buy_option(deps, &env, info, &option_id, &config)
+            }
+
+            CallInstrumentExecuteMsg::FinalizeListing { option_id } => {
+                execute::finalize_listing(deps, env, &option_id, &config)
+            }
+
+            CallInstrumentExecuteMsg::UpdateConfig {
+                expiry_range,
+                expiry_series,
+                clear_expiry_series,
+                allowed_denom,
+                min_bid_increment_bps,
+                settlement_auction_start_offset,
+                royalties_enabled,
+                keeper_bounty,
+            } => execute::update_config(
+                deps,
+                info,
+                expiry_range,
+                expiry_series,
+                clear_expiry_series.unwrap_or(false),
+                allowed_denom,
+                min_bid_increment_bps,
+                settlement_auction_start_offset,
+                royalties_enabled,
+                keeper_bounty,
+                config,
+            ),
+
             CallInstrumentExecuteMsg::ForTest {} => execute::for_test(),
         },
 
@@ -132,37 +356,146 @@ pub fn query(
     deps: Deps,
     env: Env,
     msg: cw721_base::QueryMsg<CallInstrumentQueryMsg>,
-) -> StdResult<Binary> {
-    match msg {
+) -> Result<Binary, ContractError> {
+    Ok(match msg {
         cw721_base::QueryMsg::Extension { msg } => match msg {
             CallInstrumentQueryMsg::CurrentBid { option_id } => {
-                query::current_bid(deps, &option_id)
+                query::current_bid(deps, &option_id)?
             }
 
             CallInstrumentQueryMsg::CurrentBidder { option_id } => {
-                query::current_bidder(deps, &option_id)
+                query::current_bidder(deps, &option_id)?
             }
 
             CallInstrumentQueryMsg::GetVaultAddress { option_id } => {
-                query::get_vault_address(deps, &option_id)
+                query::get_vault_address(deps, &option_id)?
             }
 
             CallInstrumentQueryMsg::GetOptionIdForAsset { vault, asset_id } => {
-                query::get_option_id_for_asset(deps, vault, &asset_id)
+                query::get_option_id_for_asset(deps, vault, &asset_id)?
             }
 
             CallInstrumentQueryMsg::GetAssetId { option_id } => {
-                query::get_asset_id(deps, &option_id)
+                query::get_asset_id(deps, &option_id)?
             }
 
             CallInstrumentQueryMsg::GetStrikePrice { option_id } => {
-                query::get_strike_price(deps, &option_id)
+                query::get_strike_price(deps, &option_id)?
             }
 
             CallInstrumentQueryMsg::GetExpiration { option_id } => {
-                query::get_expiration(deps, &option_id)
+                query::get_expiration(deps, &option_id)?
+            }
+
+            CallInstrumentQueryMsg::GetAuctionStart { option_id } => {
+                query::get_auction_start(deps, &option_id)?
+            }
+
+            CallInstrumentQueryMsg::ListOptions {
+                writer,
+                vault,
+                bidder,
+                status,
+                expires_after,
+                expires_before,
+                start_after,
+                limit,
+            } => query::list_options(
+                deps,
+                &env,
+                writer,
+                vault,
+                bidder,
+                status,
+                expires_after,
+                expires_before,
+                start_after,
+                limit,
+            )?,
+
+            CallInstrumentQueryMsg::ActiveSeries { start_after, limit } => {
+                query::active_series(deps, &env, start_after, limit)?
+            }
+
+            CallInstrumentQueryMsg::SeriesOptions {
+                expiration,
+                start_after,
+                limit,
+            } => query::series_options(deps, &env, expiration, start_after, limit)?,
+
+            CallInstrumentQueryMsg::OptionDetails { option_id } => {
+                query::option_details(deps, &env, &option_id)?
+            }
+
+            CallInstrumentQueryMsg::OptionsDetails { option_ids } => {
+                query::options_details(deps, &env, option_ids)?
+            }
+
+            CallInstrumentQueryMsg::PendingRefunds { address, denom } => {
+                query::pending_refunds(deps, address, denom)?
+            }
+
+            CallInstrumentQueryMsg::OptionTerms { option_id } => {
+                query::option_terms(deps, &option_id)?
+            }
+
+            CallInstrumentQueryMsg::BidCommitment { option_id, bidder } => {
+                query::bid_commitment(deps, &option_id, bidder)?
+            }
+
+            CallInstrumentQueryMsg::SettlementPreview { option_id } => {
+                query::settlement_preview(deps, &option_id)?
+            }
+
+            CallInstrumentQueryMsg::Listing { option_id } => query::listing(deps, &option_id)?,
+
+            CallInstrumentQueryMsg::BurnBounty { option_id } => {
+                query::burn_bounty(deps, &option_id)?
             }
         },
-        _ => CallInstrumentContract::default().query(deps, env, msg),
+
+        // serve the generated option image as token uri
+        cw721_base::QueryMsg::NftInfo { token_id } => query::nft_info(deps, token_id)?,
+
+        cw721_base::QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => query::all_nft_info(deps, env, token_id, include_expired.unwrap_or(false))?,
+
+        _ => CallInstrumentContract::default().query(deps, env, msg)?,
+    })
+}
+
+/// The entry point to the contract for processing replies from submessages.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match (msg.id, msg.result) {
+        (id, result) if id >= REFUND_REPLY_ID => execute::refund_replied(deps, id, result),
+        _ => Err(ContractError::UnknownReplyID {}),
     }
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = set_migrated_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if previous < Version::new(0, 2, 0) {
+        let (Some(settlement_auction_start_offset), Some(maximum_option_duration)) = (
+            msg.settlement_auction_start_offset,
+            msg.maximum_option_duration,
+        ) else {
+            return Err(ContractError::InvalidConfig {});
+        };
+        migrations::migrate_from_v0_1(
+            deps.storage,
+            deps.api,
+            settlement_auction_start_offset,
+            maximum_option_duration,
+        )?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", previous.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
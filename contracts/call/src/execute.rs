@@ -1,45 +1,70 @@
 use cosmwasm_std::{
-    ensure, to_binary, Addr, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Response, StdError,
-    Uint128, WasmMsg,
+    ensure, from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, Event,
+    MessageInfo, Order, Response, StdResult, Storage, SubMsg, SubMsgResult, Uint128,
 };
+use cw20::Cw20ReceiveMsg;
+use cw721::Approval;
 use cw_utils::{nonpayable, Expiration};
 
 use common::{
-    denom::find_allowed_coin,
+    denom::{find_allowed_coin, Denom},
     errors::ContractError,
-    msg::bank_send_msg,
+    events::{
+        AssetReclaimed, BidPlaced, BidRefunded, BurnBountyFunded, ExpiredOptionBurned,
+        ListingBidPlaced, ListingBidRefunded, ListingCancelled, OptionAmended, OptionExercised,
+        OptionListed, OptionSettled, OptionSold, ProceedsClaimed,
+    },
     nft,
-    types::{AssetId, OptionId, TokenId},
+    types::{AssetId, ExerciseStyle, ExpiryRange, ExpirySeries, OptionId, TokenId},
 };
+use protocol::helpers::{assert_not_paused, assert_protocol_owner};
 use vault::{
-    msg::{
-        CurrentEntitlementOperatorResponse, ExecuteMsg as VaultExecuteMsg,
-        QueryMsg as VaultQueryMsg, SetEntitlement,
-    },
+    msg::{CurrentEntitlementOperatorResponse, QueryMsg as VaultQueryMsg, SetEntitlement},
     utils::{
         clear_entitlement_and_distribute_wasm_msg, clear_entitlement_wasm_msg,
-        set_beneficial_owner_wasm_msg,
+        impose_entitlement_wasm_msg, set_beneficial_owner_wasm_msg,
     },
 };
 use vault_factory::msg::QueryMsg;
 
 use crate::{
-    state::{CallInstrument, Config, OPTION_CLAIMS},
-    utils::{burn_option_nft, is_beneficial_owner_or_operator, mint_call, option_owner},
+    contract::CallInstrumentContract,
+    msg::{
+        AuctionMode, ListingKind, MintBatchEntry, MintBatchResponse, ReceiveMsg, UnrevealedDeposits,
+    },
+    state::{
+        BidCommitment, CallInstrument, Config, Listing, SealedAuction, BID_COMMITMENTS,
+        BURN_BOUNTIES, LISTINGS, OPTION_CLAIMS, PENDING_REFUNDS, REFUNDS_IN_FLIGHT,
+        SEALED_AUCTIONS,
+    },
+    utils::{
+        auction_start, bid_commitment, burn_option_nft, entitlement_expiration,
+        extended_auction_end, is_beneficial_owner_or_operator, is_entitled_operator, min_next_bid,
+        mint_call, option_minted_event, option_owner, protocol_config, reveal_end,
+        settlement_breakdown, settlement_royalty, sync_option_metadata, transfer_option_nft,
+    },
 };
 
+/// The first `reply` call code ID of refund sub-messages, each refund in flight is sent with its own ID
+/// from there on.
+pub(crate) const REFUND_REPLY_ID: u64 = 1;
+
+/// The maximum number of options minted by a single batch.
+const MAX_BATCH_SIZE: usize = 30;
+
 // MESSAGE HANDLERS
 
 /// Mints a new call option for a particular "underlying" NFT with a given strike price and expiration.
 #[allow(clippy::too_many_arguments)]
 pub fn mint_with_nft(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: &Env,
     info: MessageInfo,
     nft_addr: String,
     nft_id: TokenId,
     strike: Uint128,
     expiration: Expiration,
+    exercise_style: ExerciseStyle,
     config: &Config,
 ) -> Result<Response, ContractError> {
     // let nft_addr = into_addr(deps.api, nft, PREFIX)?;
@@ -47,84 +72,231 @@ pub fn mint_with_nft(
 
     // check that sender uses allowed nft
     if config.allowed_underlying_nft != nft_addr {
-        return Err(StdError::generic_err("mint_with_nft - nft not allowed").into());
+        return Err(ContractError::NftNotAllowed {});
     }
 
-    // check if the sender is an owner, or has a approval, or is an nft operator
-    let owner = nft::owner_of(&deps.querier, &nft_addr, &nft_id)?.owner;
-    let operators = nft::all_operators(&deps.querier, &owner, &nft_addr, None, None, false)?;
-    let approvals = nft::approvals(&deps.querier, &nft_addr, &nft_id, None)?.approvals;
-    let is_sender_operator = operators.iter().any(|a| a.spender == info.sender);
-    let is_sender_has_approval = approvals.iter().any(|a| a.spender == info.sender);
-    let is_contract_operator = operators.iter().any(|a| a.spender == env.contract.address);
-    let is_contract_has_approval = approvals.iter().any(|a| a.spender == env.contract.address);
-
-    ensure!(
-        owner == info.sender || is_sender_operator || is_sender_has_approval,
-        StdError::generic_err("mint_with_nft - caller not owner or operator")
-    );
-    ensure!(
-        is_contract_operator || is_contract_has_approval,
-        StdError::generic_err("mint_with_nft - call option contract not operator or has approval",)
-    );
-
-    // find appropriate vault
-    let vault_addr = deps
-        .querier
-        .query_wasm_smart::<Option<Addr>>(
-            &config.vault_factory_addr,
-            &QueryMsg::GetMultiOrSoloVault {
-                nft_addr: nft_addr.as_str().into(),
-                nft_id: Some(nft_id.clone()),
-            },
-        )?
-        .ok_or(StdError::generic_err(
-            "mint_with_nft - appropriate vault not found",
-        ))?;
+    let (owner, vault_addr) =
+        prepare_mint_with_nft(deps.as_ref(), env, &info.sender, &nft_addr, &nft_id, config)?;
 
     let new_option_id = mint_call(
-        deps,
+        deps.branch(),
         env,
         &owner,
         &vault_addr,
         &nft_id,
         strike,
         expiration,
+        exercise_style,
         config,
     )?;
 
-    let set_entitlement = SetEntitlement {
-        beneficial_owner: info.sender,
-        entitled_operator: env.contract.address.clone(),
-        approved_operator: None,
-        expiry: expiration,
-    };
-
-    // send the underlying asset into our vault, passing along the entitlement. The entitlement specified
-    // here will be accepted by the vault because we are also simultaneously tendering the asset.
-    let send_nft_msg = nft::send_nft(
+    let send_nft_msg = send_nft_to_vault_msg(
+        env,
+        &info.sender,
         &nft_addr,
         &nft_id,
         &vault_addr,
-        to_binary(&Some(set_entitlement))?,
+        entitlement_expiration(&expiration, config),
     )?;
 
     Ok(Response::new()
         .add_submessage(send_nft_msg)
+        .add_event(option_minted_event(deps.storage, &new_option_id, config)?)
         .add_attribute("action", "mint_with_nft")
         .add_attribute("option_id", new_option_id.to_string()))
 }
 
+/// Mints a call option for each entry, where the asset id is the token id of an NFT of the allowed collection.
+/// All entries are validated before any option is minted.
+pub(crate) fn mint_batch_with_nft(
+    mut deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    nft_addr: String,
+    entries: Vec<MintBatchEntry>,
+    exercise_style: ExerciseStyle,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    ensure_batch_size(&entries)?;
+    ensure_unique_assets(&entries)?;
+
+    let nft_addr = deps.api.addr_validate(&nft_addr)?;
+    if config.allowed_underlying_nft != nft_addr {
+        return Err(ContractError::NftNotAllowed {});
+    }
+
+    // the multi vault and the operators of each owner are the same for every entry of the batch
+    let multi_vault = find_vault(deps.as_ref(), &nft_addr, None, config)?;
+    let mut operators: Vec<(String, Vec<Approval>)> = vec![];
+    let mut prepared = vec![];
+    for entry in &entries {
+        let owner = nft::owner_of(&deps.querier, &nft_addr, &entry.asset_id)?.owner;
+        let index = match operators.iter().position(|(o, _)| *o == owner) {
+            Some(index) => index,
+            None => {
+                let owner_operators =
+                    nft::all_operators(&deps.querier, &owner, &nft_addr, None, None, false)?;
+                operators.push((owner.clone(), owner_operators));
+                operators.len() - 1
+            }
+        };
+        ensure_mint_approvals(
+            deps.as_ref(),
+            env,
+            &info.sender,
+            &nft_addr,
+            &entry.asset_id,
+            &owner,
+            &operators[index].1,
+        )?;
+
+        let vault_addr = match &multi_vault {
+            Some(vault_addr) => vault_addr.clone(),
+            None => find_vault(deps.as_ref(), &nft_addr, Some(&entry.asset_id), config)?
+                .ok_or(ContractError::VaultNotFound(entry.asset_id.clone()))?,
+        };
+        prepared.push((owner, vault_addr));
+    }
+
+    let mut option_ids = vec![];
+    let mut send_nft_msgs = vec![];
+    for (entry, (owner, vault_addr)) in entries.into_iter().zip(prepared) {
+        option_ids.push(mint_call(
+            deps.branch(),
+            env,
+            &owner,
+            &vault_addr,
+            &entry.asset_id,
+            entry.strike,
+            entry.expiration,
+            exercise_style.clone(),
+            config,
+        )?);
+        send_nft_msgs.push(send_nft_to_vault_msg(
+            env,
+            &info.sender,
+            &nft_addr,
+            &entry.asset_id,
+            &vault_addr,
+            entitlement_expiration(&entry.expiration, config),
+        )?);
+    }
+
+    let events = option_ids
+        .iter()
+        .map(|option_id| option_minted_event(deps.storage, option_id, config))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_submessages(send_nft_msgs)
+        .add_events(events)
+        .add_attribute("action", "mint_batch_with_nft")
+        .add_attribute("option_ids", join_option_ids(&option_ids))
+        .set_data(to_binary(&MintBatchResponse { option_ids })?))
+}
+
+/// Checks that the sender may write an option on the NFT and finds the vault it will be deposited into.
+/// Returns the owner of the NFT, who becomes the writer, and the vault address.
+fn prepare_mint_with_nft(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    nft_addr: &Addr,
+    nft_id: &TokenId,
+    config: &Config,
+) -> Result<(String, Addr), ContractError> {
+    let owner = nft::owner_of(&deps.querier, nft_addr, nft_id)?.owner;
+    let operators = nft::all_operators(&deps.querier, &owner, nft_addr, None, None, false)?;
+    ensure_mint_approvals(deps, env, sender, nft_addr, nft_id, &owner, &operators)?;
+
+    let vault_addr = find_vault(deps, nft_addr, Some(nft_id), config)?
+        .ok_or(ContractError::VaultNotFound(nft_id.clone()))?;
+
+    Ok((owner, vault_addr))
+}
+
+/// Checks that the sender is the owner of the NFT, or one of its operators or approved spenders,
+/// and that the instrument is allowed to send it into the vault.
+fn ensure_mint_approvals(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    nft_addr: &Addr,
+    nft_id: &TokenId,
+    owner: &String,
+    operators: &[Approval],
+) -> Result<(), ContractError> {
+    // check if the sender is an owner, or has a approval, or is an nft operator
+    let approvals = nft::approvals(&deps.querier, nft_addr, nft_id, None)?.approvals;
+    let is_sender_operator = operators.iter().any(|a| a.spender == *sender);
+    let is_sender_has_approval = approvals.iter().any(|a| a.spender == *sender);
+    let is_contract_operator = operators.iter().any(|a| a.spender == env.contract.address);
+    let is_contract_has_approval = approvals.iter().any(|a| a.spender == env.contract.address);
+
+    ensure!(
+        *owner == *sender || is_sender_operator || is_sender_has_approval,
+        ContractError::NotOwnerOrOperator {}
+    );
+    ensure!(
+        is_contract_operator || is_contract_has_approval,
+        ContractError::InstrumentNotApproved {}
+    );
+
+    Ok(())
+}
+
+/// Finds the multi vault of the collection, or the solo vault of the NFT if one is given.
+fn find_vault(
+    deps: Deps,
+    nft_addr: &Addr,
+    nft_id: Option<&TokenId>,
+    config: &Config,
+) -> StdResult<Option<Addr>> {
+    deps.querier.query_wasm_smart(
+        &config.vault_factory_addr,
+        &QueryMsg::GetMultiOrSoloVault {
+            nft_addr: nft_addr.as_str().into(),
+            nft_id: nft_id.cloned(),
+        },
+    )
+}
+
+/// Sends the underlying asset into the vault, passing along the entitlement. The entitlement specified
+/// here will be accepted by the vault because we are also simultaneously tendering the asset.
+fn send_nft_to_vault_msg(
+    env: &Env,
+    sender: &Addr,
+    nft_addr: &Addr,
+    nft_id: &TokenId,
+    vault_addr: &Addr,
+    expiration: Expiration,
+) -> StdResult<SubMsg> {
+    let set_entitlement = SetEntitlement {
+        beneficial_owner: sender.clone(),
+        entitled_operator: env.contract.address.clone(),
+        approved_operator: None,
+        expiry: expiration,
+    };
+
+    nft::send_nft(
+        nft_addr,
+        nft_id,
+        vault_addr,
+        to_binary(&Some(set_entitlement))?,
+    )
+}
+
 /// Mints a new call option for the assets deposited in a particular vault given strike price and expiration.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn mint_with_vault(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     vault: String,
     asset_id: AssetId,
     strike: Uint128,
     expiration: Expiration,
+    exercise_style: ExerciseStyle,
     config: &Config,
 ) -> Result<Response, ContractError> {
     deps.api.addr_validate(&vault)?;
@@ -135,74 +307,180 @@ pub(crate) fn mint_with_vault(
         .querier
         .query_wasm_smart(&vault, &VaultQueryMsg::AssetAddress {})?;
     if config.allowed_underlying_nft != nft_addr {
-        return Err(StdError::generic_err("mint_with_vault - nft not allowes").into());
-    }
-
-    // check that asset already in the vault
-    if !deps.querier.query_wasm_smart::<bool>(
-        &vault,
-        &VaultQueryMsg::HoldsAsset {
-            asset_id: asset_id.clone(),
-        },
-    )? {
-        return Err(StdError::generic_err("mint_with_vault - asset not in vault").into());
+        return Err(ContractError::NftNotAllowed {});
     }
 
     // TODO verify that a particular vault was created by the protocol's vault factory
     // _allowedVaultImplementation(vaultAddress, allowedUnderlyingAddress, assetId)
 
-    // the beneficial owner is the only one able to impose entitlements, so
-    // we need to require that they've done so here
-    let (ok, beneficial_owner) =
-        is_beneficial_owner_or_operator(&deps.querier, &vault, asset_id.clone(), &info.sender)?;
-    ensure!(
-        ok,
-        StdError::generic_err(
-            "mint_with_vault - called by someone other than the beneficial owner or operator",
-        )
-    );
-    let beneficial_owner = beneficial_owner.ok_or(StdError::generic_err(
-        "mint_with_entitled_vault - beneficial owner not set",
-    ))?;
+    let beneficial_owner = prepare_mint_with_vault(deps.as_ref(), &info.sender, &vault, &asset_id)?;
 
     let new_option_id = mint_call(
-        deps,
+        deps.branch(),
         &env,
         beneficial_owner,
         &vault,
         &asset_id,
         strike,
         expiration,
+        exercise_style,
         config,
     )?;
 
-    let impose_entitlement_msg = CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
-        contract_addr: vault,
-        msg: to_binary(&VaultExecuteMsg::ImposeEntitlement {
-            operator: env.contract.address.into_string(),
-            expiry: expiration,
-            asset_id,
-        })?,
-        funds: vec![],
-    });
+    let impose_entitlement_msg = impose_entitlement_wasm_msg(
+        &vault,
+        &asset_id,
+        env.contract.address.as_str(),
+        entitlement_expiration(&expiration, config),
+    )?;
 
     Ok(Response::new()
         .add_message(impose_entitlement_msg)
+        .add_event(option_minted_event(deps.storage, &new_option_id, config)?)
         .add_attribute("action", "mint_with_vault")
         .add_attribute("option_id", new_option_id.to_string()))
 }
 
+/// Mints a call option for each entry on the assets deposited in a particular vault.
+/// All entries are validated before any option is minted.
+pub(crate) fn mint_batch_with_vault(
+    mut deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    vault: String,
+    entries: Vec<MintBatchEntry>,
+    exercise_style: ExerciseStyle,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    ensure_batch_size(&entries)?;
+    ensure_unique_assets(&entries)?;
+
+    deps.api.addr_validate(&vault)?;
+
+    // check that sender uses allowed nft
+    let nft_addr: Addr = deps
+        .querier
+        .query_wasm_smart(&vault, &VaultQueryMsg::AssetAddress {})?;
+    if config.allowed_underlying_nft != nft_addr {
+        return Err(ContractError::NftNotAllowed {});
+    }
+
+    let writers = entries
+        .iter()
+        .map(|entry| prepare_mint_with_vault(deps.as_ref(), &info.sender, &vault, &entry.asset_id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut option_ids = vec![];
+    let mut impose_entitlement_msgs = vec![];
+    for (entry, writer) in entries.into_iter().zip(writers) {
+        option_ids.push(mint_call(
+            deps.branch(),
+            env,
+            writer,
+            &vault,
+            &entry.asset_id,
+            entry.strike,
+            entry.expiration,
+            exercise_style.clone(),
+            config,
+        )?);
+        impose_entitlement_msgs.push(impose_entitlement_wasm_msg(
+            &vault,
+            &entry.asset_id,
+            env.contract.address.as_str(),
+            entitlement_expiration(&entry.expiration, config),
+        )?);
+    }
+
+    let events = option_ids
+        .iter()
+        .map(|option_id| option_minted_event(deps.storage, option_id, config))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(impose_entitlement_msgs)
+        .add_events(events)
+        .add_attribute("action", "mint_batch_with_vault")
+        .add_attribute("option_ids", join_option_ids(&option_ids))
+        .set_data(to_binary(&MintBatchResponse { option_ids })?))
+}
+
+/// Checks that the asset is in the vault and the sender is its beneficial owner or operator.
+/// Returns the beneficial owner, who becomes the writer.
+fn prepare_mint_with_vault(
+    deps: Deps,
+    sender: &Addr,
+    vault: &str,
+    asset_id: &AssetId,
+) -> Result<Addr, ContractError> {
+    // check that asset already in the vault
+    if !deps.querier.query_wasm_smart::<bool>(
+        vault,
+        &VaultQueryMsg::HoldsAsset {
+            asset_id: asset_id.clone(),
+        },
+    )? {
+        return Err(ContractError::AssetNotInVault(asset_id.clone()));
+    }
+
+    // the beneficial owner is the only one able to impose entitlements, so
+    // we need to require that they've done so here
+    let (ok, beneficial_owner) = is_beneficial_owner_or_operator(
+        &deps.querier,
+        &vault.to_string(),
+        asset_id.clone(),
+        sender,
+    )?;
+    ensure!(ok, ContractError::OnlyBeneficialOwnerOrOperator {});
+
+    beneficial_owner.ok_or(ContractError::BeneficialOwnerNotSet {})
+}
+
+fn ensure_batch_size<T>(entries: &[T]) -> Result<(), ContractError> {
+    ensure!(
+        !entries.is_empty() && entries.len() <= MAX_BATCH_SIZE,
+        ContractError::InvalidBatchSize {
+            max: MAX_BATCH_SIZE as u64
+        }
+    );
+
+    Ok(())
+}
+
+/// Rejects a batch minting more than one option on the same asset.
+fn ensure_unique_assets(entries: &[MintBatchEntry]) -> Result<(), ContractError> {
+    for (i, entry) in entries.iter().enumerate() {
+        ensure!(
+            !entries[..i]
+                .iter()
+                .any(|other| other.asset_id == entry.asset_id),
+            ContractError::DuplicateAsset(entry.asset_id.clone())
+        );
+    }
+
+    Ok(())
+}
+
+fn join_option_ids(option_ids: &[OptionId]) -> String {
+    option_ids
+        .iter()
+        .map(|option_id| option_id.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 /// Mints a new call option for the assets deposited in a particular vault given strike price and expiration.
 /// That vault must already have a registered entitlement for this contract with the an expiration equal to {expirationTime}
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn mint_with_entitled_vault(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: &Env,
     info: MessageInfo,
     vault: &String,
     asset_id: AssetId,
     strike: Uint128,
     expiration: Expiration,
+    exercise_style: ExerciseStyle,
     config: &Config,
 ) -> Result<Response, ContractError> {
     // check that sender uses allowed nft
@@ -210,7 +488,7 @@ pub(crate) fn mint_with_entitled_vault(
         .querier
         .query_wasm_smart(vault, &VaultQueryMsg::AssetAddress {})?;
     if config.allowed_underlying_nft != nft_addr {
-        return Err(StdError::generic_err("mint_with_vault - nft not allowes").into());
+        return Err(ContractError::NftNotAllowed {});
     }
 
     // check that asset already in the vault
@@ -220,7 +498,7 @@ pub(crate) fn mint_with_entitled_vault(
             asset_id: asset_id.clone(),
         },
     )? {
-        return Err(StdError::generic_err("mint_with_vault - asset not in vault").into());
+        return Err(ContractError::AssetNotInVault(asset_id.clone()));
     }
 
     // only current operator can mint option with already entitled asset
@@ -234,128 +512,200 @@ pub(crate) fn mint_with_entitled_vault(
         },
     )?;
     if !is_active && operator.map_or(true, |op| op != env.contract.address) {
-        return Err(
-            StdError::generic_err("mint_with_entitled_vault - call contract not operator").into(),
-        );
+        return Err(ContractError::InstrumentNotOperator {});
     }
 
     // expiration must be equal
-    let entitlement_expiration: Option<Expiration> = deps.querier.query_wasm_smart(
+    let expected_expiration = entitlement_expiration(&expiration, config);
+    let current_expiration: Option<Expiration> = deps.querier.query_wasm_smart(
         vault,
         &VaultQueryMsg::EntitlementExpiration {
             asset_id: asset_id.clone(),
         },
     )?;
-    if entitlement_expiration.map_or(true, |e| e != expiration) {
-        return Err(StdError::generic_err(
-            "mint_with_entitled_vault - entitlement expiration different",
-        )
-        .into());
+    if current_expiration.map_or(true, |e| e != expected_expiration) {
+        return Err(ContractError::EntitlementExpirationMismatch {});
     }
 
     // the beneficial owner owns the asset so they should receive the option
     let (ok, writer) =
         is_beneficial_owner_or_operator(&deps.querier, vault, asset_id.clone(), &info.sender)?;
     if !ok {
-        return Err(StdError::generic_err(
-            "mint_with_entitled_vault - only owner or operator may mint",
-        )
-        .into());
+        return Err(ContractError::OnlyBeneficialOwnerOrOperator {});
     }
 
     // TODO we need this checks?
-    let writer_addr = writer.ok_or(StdError::generic_err(
-        "mint_with_entitled_vault - beneficial owner not set",
-    ))?;
+    let writer_addr = writer.ok_or(ContractError::BeneficialOwnerNotSet {})?;
 
     let new_option_id = mint_call(
-        deps,
+        deps.branch(),
         env,
         writer_addr,
         vault,
         &asset_id,
         strike,
         expiration,
+        exercise_style,
         config,
     )?;
 
     Ok(Response::new()
+        .add_event(option_minted_event(deps.storage, &new_option_id, config)?)
         .add_attribute("action", "mint_with_entitled_vault")
         .add_attribute("option_id", new_option_id.to_string()))
 }
 
+/// Bid in the settlement auction with native funds attached to the message.
 pub(crate) fn bid(
     deps: DepsMut,
+    env: &Env,
     info: MessageInfo,
     option_id: &OptionId,
     config: &Config,
 ) -> Result<Response, ContractError> {
-    // TODO use this macros or use if?
-    // ensure!(
-    //     !has_allowed_denoms(&info.funds, &config.allowed_denoms),
-    //     Err(StdError::generic_err(
-    //         "bid - not allowed denom",
-    //     ));
-    // );
-    let mut new_bid = find_allowed_coin(info.funds.clone(), config.allowed_denom.as_ref())
+    let new_bid = find_allowed_coin(info.funds.clone(), &config.allowed_denom)
         .ok_or(ContractError::DenomNotAllowed {})?;
 
-    let mut call = CallInstrument::load(deps.storage, option_id)?;
+    place_bid(deps, env, info.sender, new_bid.amount, option_id, config)
+}
 
-    if info.sender == call.writer_addr {
-        // Handle the case where an option writer bids on an underlying asset that they owned.
-        // In this case, as they would be the recipient of the spread after the auction,
-        // they are able to bid paying only the difference between their bid and the strike.
-        new_bid.amount += call.strike;
+/// Handles cw20 tokens sent to the instrument. Only the cw20 token of the option's terms is accepted.
+pub(crate) fn receive_cw20(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    let option_id = match &msg {
+        ReceiveMsg::Bid { option_id }
+        | ReceiveMsg::Exercise { option_id }
+        | ReceiveMsg::CommitBid { option_id, .. }
+        | ReceiveMsg::BuyOption { option_id }
+        | ReceiveMsg::FundBurnBounty { option_id } => option_id,
+    };
+    // the same messages as their native counterparts are not accepted while the protocol is paused
+    if matches!(
+        msg,
+        ReceiveMsg::Bid { .. } | ReceiveMsg::CommitBid { .. } | ReceiveMsg::BuyOption { .. }
+    ) {
+        assert_not_paused(&deps.querier, &config.protocol_addr)?;
+    }
+    let config = &config.for_option(deps.storage, option_id)?;
+
+    // info.sender - cw20 token contract
+    // wrapper.sender - owner of the sent tokens
+    ensure!(
+        config.allowed_denom == Denom::Cw20(info.sender),
+        ContractError::DenomNotAllowed {}
+    );
+
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+
+    match msg {
+        ReceiveMsg::Bid { option_id } => {
+            place_bid(deps, env, sender, wrapper.amount, &option_id, config)
+        }
+        ReceiveMsg::Exercise { option_id } => {
+            exercise_option(deps, env, sender, wrapper.amount, &option_id, config)
+        }
+        ReceiveMsg::CommitBid {
+            option_id,
+            commitment,
+        } => place_commitment(
+            deps,
+            env,
+            sender,
+            wrapper.amount,
+            &option_id,
+            commitment,
+            config,
+        ),
+        ReceiveMsg::BuyOption { option_id } => {
+            purchase_option(deps, env, sender, wrapper.amount, &option_id, config)
+        }
+        ReceiveMsg::FundBurnBounty { option_id } => {
+            add_burn_bounty(deps, sender, wrapper.amount, &option_id, config)
+        }
     }
+}
 
+fn place_bid(
+    deps: DepsMut,
+    env: &Env,
+    bidder: Addr,
+    amount: Uint128,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Response, ContractError> {
     ensure!(
-        call.bid.u128() / 10_000 * 10_000 == call.bid.u128(),
-        StdError::generic_err("bid - bid amount too small")
-    ); // TODO why am I doing it?
-    let bid_increment: u128 =
-        (call.bid.u128() * config.min_bid_increment_bps as u128) / 10_000_u128;
-    let min_required_amount = call.bid.checked_add(bid_increment.into())?;
+        config.auction_mode == AuctionMode::Open,
+        ContractError::SealedBidAuction {}
+    );
 
-    // min_required_amount = call_bid + ((call_bid * min_bid_increment_bips) / 10000)
-    // let network_fee = payment * params.trading_fee_percent / Uint128::from(100u128);
-    // new_bid.amount.multiply_ratio(90u128, 100u128);
+    let mut bid_amount = amount;
 
-    // 1_000_000_000_000_000_000
-    // let bid_increment = call
-    //     .bid
-    //     .checked_mul(Decimal::percent(config.min_bid_increment_bps))?
-    //     .checked_div(Uint128::new(10000))?;
-    // let min_required_amount = call.bid.checked_add(bid_increment)?;
+    let mut call = CallInstrument::load(deps.storage, option_id)?;
 
+    // bids are only accepted within the settlement auction window
+    ensure!(
+        auction_start(&call.expiration, config.settlement_auction_start_offset)
+            .is_expired(&env.block),
+        ContractError::AuctionNotStarted {}
+    );
     ensure!(
-        new_bid.amount >= min_required_amount,
-        StdError::generic_err("bid - must overbid by minBidIncrementBips")
+        !call.auction_end.is_expired(&env.block),
+        ContractError::OptionIsExpired {}
     );
 
+    if bidder == call.writer_addr {
+        // Handle the case where an option writer bids on an underlying asset that they owned.
+        // In this case, as they would be the recipient of the spread after the auction,
+        // they are able to bid paying only the difference between their bid and the strike.
+        bid_amount += call.strike;
+    }
+
     ensure!(
-        new_bid.amount >= call.strike,
+        bid_amount >= call.strike,
         ContractError::BidIsLowerStrikePrice {}
     );
 
+    let min_bid = min_next_bid(&call, config.min_bid_increment_bps)?;
+    ensure!(
+        bid_amount >= min_bid,
+        ContractError::BidIncrementTooSmall { min_bid }
+    );
+
     let resp = Response::new()
         .add_attribute("action", "bid")
-        .add_attribute("bid_amount", new_bid.amount);
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("bid_amount", bid_amount);
 
     // return bid to previous bidder
     let resp = match call.bidder {
         Some(high_bidder) => {
-            let bid_to_return = call.bid;
-            if high_bidder == call.writer_addr {
-                bid_to_return.checked_sub(call.strike)?;
-            }
+            let bid_to_return = if high_bidder == call.writer_addr {
+                call.bid.checked_sub(call.strike)?
+            } else {
+                call.bid
+            };
             if bid_to_return > Uint128::zero() {
-                // handle the case when high_bidder is Some and bid_to_return is greater than zero
-                let return_bid_msg = bank_send_msg(
-                    high_bidder.into_string(),
-                    config.allowed_denom.coins(&bid_to_return),
-                );
-                resp.add_message(return_bid_msg)
+                resp.add_submessage(refund_msg(
+                    deps.storage,
+                    config,
+                    high_bidder.clone(),
+                    bid_to_return,
+                )?)
+                .add_event(
+                    BidRefunded {
+                        option_id: *option_id,
+                        bidder: high_bidder,
+                        amount: bid_to_return,
+                        denom: config.allowed_denom.clone(),
+                    }
+                    .into(),
+                )
             } else {
                 resp
             }
@@ -364,119 +714,963 @@ pub(crate) fn bid(
     };
 
     // set the new bidder
-    call.bid = new_bid.amount;
-    call.bidder = Some(info.sender.clone());
+    call.bid = bid_amount;
+    call.bidder = Some(bidder.clone());
+
+    // a bid close to the end of the auction extends it, so the auction can't be sniped
+    let auction_end = config
+        .anti_snipe
+        .as_ref()
+        .and_then(|anti_snipe| extended_auction_end(&call, anti_snipe, &env.block));
+    if let Some(auction_end) = auction_end {
+        call.auction_end = auction_end;
+    }
+
     call.save(deps.storage, option_id)?;
+    sync_option_metadata(deps.storage, option_id, &call, config)?;
 
     // the new high bidder is the beneficial owner of the asset.
     // the beneficial owner must be set here instead of with a settlement
     // because otherwise the writer will be able to remove the asset from the vault
     // between the expiration and the settlement call, effectively stealing the asset.
-    let resp = resp.add_message(set_beneficial_owner_wasm_msg(
-        call.vault_addr.as_str(),
-        call.asset_id.as_str(),
-        info.sender.as_str(),
-    )?);
+    let mut resp = resp
+        .add_message(set_beneficial_owner_wasm_msg(
+            call.vault_addr.as_str(),
+            call.asset_id.as_str(),
+            bidder.as_str(),
+        )?)
+        .add_event(
+            BidPlaced {
+                option_id: *option_id,
+                bidder,
+                amount: bid_amount,
+                denom: config.allowed_denom.clone(),
+                auction_end: call.auction_end,
+            }
+            .into(),
+        );
+
+    // the entitlement is extended with the auction, so the writer can't pull the asset in the meantime
+    if let Some(auction_end) = auction_end {
+        resp = resp
+            .add_messages(reimpose_entitlement_msgs(env, &call, config)?)
+            .add_attribute("auction_end", auction_end.to_string());
+    }
 
     Ok(resp)
 }
 
-/// Allows the writer to reclaim an entitled asset. This is only possible
-/// when the writer holds the option nft and calls this function.
-pub(crate) fn reclaim_asset(
+/// Commit a sealed bid in the settlement auction with native funds attached to the message as the deposit.
+pub(crate) fn commit_bid(
     deps: DepsMut,
-    env: Env,
+    env: &Env,
     info: MessageInfo,
     option_id: &OptionId,
-    withdraw: bool,
+    commitment: Binary,
     config: &Config,
 ) -> Result<Response, ContractError> {
-    nonpayable(&info)?; // TODO ???
+    let deposit = find_allowed_coin(info.funds.clone(), &config.allowed_denom)
+        .ok_or(ContractError::DenomNotAllowed {})?;
 
-    let mut call = CallInstrument::load(deps.storage, option_id)?;
+    place_commitment(
+        deps,
+        env,
+        info.sender,
+        deposit.amount,
+        option_id,
+        commitment,
+        config,
+    )
+}
 
+/// Escrows the deposit of a sealed bid committed within the settlement auction window.
+fn place_commitment(
+    deps: DepsMut,
+    env: &Env,
+    bidder: Addr,
+    deposit: Uint128,
+    option_id: &OptionId,
+    commitment: Binary,
+    config: &Config,
+) -> Result<Response, ContractError> {
     ensure!(
-        call.writer_addr == info.sender,
-        ContractError::Unauthorized {} // TODO change type error
+        matches!(config.auction_mode, AuctionMode::SealedBid { .. }),
+        ContractError::OpenAuction {}
     );
+
+    let call = CallInstrument::load(deps.storage, option_id)?;
+
     ensure!(
         !call.settled,
         ContractError::OptionAlreadySettled(option_id.to_owned())
     );
+    ensure!(
+        auction_start(&call.expiration, config.settlement_auction_start_offset)
+            .is_expired(&env.block),
+        ContractError::AuctionNotStarted {}
+    );
     ensure!(
         !call.expiration.is_expired(&env.block),
         ContractError::OptionIsExpired {}
     );
-
-    let owner_option = option_owner(&deps, &env, option_id.to_string())?;
     ensure!(
-        call.writer_addr.to_owned().into_string() == owner_option,
-        StdError::generic_err("reclaim_asset - writer must own option",)
+        !BID_COMMITMENTS.has(deps.storage, (*option_id, &bidder)),
+        ContractError::BidAlreadyCommitted {}
     );
+    ensure!(!deposit.is_zero(), ContractError::ZeroDeposit {});
+
+    BID_COMMITMENTS.save(
+        deps.storage,
+        (*option_id, &bidder),
+        &BidCommitment {
+            commitment,
+            deposit,
+            revealed_bid: None,
+        },
+    )?;
 
-    // settle the option
-    call.settled = true;
-    call.save(deps.storage, option_id)?;
-
-    // burn the option NFT
-    burn_option_nft(deps, env, info, option_id.to_string())?;
-
-    let mut msgs = vec![];
+    Ok(Response::new()
+        .add_attribute("action", "commit_bid")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("bidder", bidder)
+        .add_attribute("deposit", deposit))
+}
 
-    // return current bidder's money
-    if let Some(high_bidder) = call.bidder {
+/// Reveals a sealed bid after the expiration of the option. The highest revealed bid becomes
+/// the high bid of the option and its bidder the beneficial owner of the asset.
+pub(crate) fn reveal_bid(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    amount: Uint128,
+    salt: String,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let AuctionMode::SealedBid { reveal_period, .. } = config.auction_mode else {
+        return Err(ContractError::OpenAuction {});
+    };
+
+    let mut call = CallInstrument::load(deps.storage, option_id)?;
+
+    ensure!(
+        !call.settled,
+        ContractError::OptionAlreadySettled(option_id.to_owned())
+    );
+    ensure!(
+        call.expiration.is_expired(&env.block),
+        ContractError::OptionNotExpired(option_id.to_owned())
+    );
+    ensure!(
+        !reveal_end(&call.expiration, reveal_period).is_expired(&env.block),
+        ContractError::RevealPeriodOver {}
+    );
+
+    let mut commitment = BID_COMMITMENTS
+        .may_load(deps.storage, (*option_id, &info.sender))?
+        .ok_or(ContractError::NoCommittedBid {})?;
+    ensure!(
+        commitment.revealed_bid.is_none(),
+        ContractError::BidAlreadyRevealed {}
+    );
+    ensure!(
+        commitment.commitment == bid_commitment(&info.sender, option_id, amount, &salt),
+        ContractError::CommitmentMismatch {}
+    );
+    ensure!(
+        amount <= commitment.deposit,
+        ContractError::DepositTooLow {}
+    );
+
+    // as in open auctions, the writer bids paying only the difference between their bid and the strike
+    let bid = if info.sender == call.writer_addr {
+        amount.checked_add(call.strike)?
+    } else {
+        amount
+    };
+    commitment.revealed_bid = Some(bid);
+    BID_COMMITMENTS.save(deps.storage, (*option_id, &info.sender), &commitment)?;
+
+    let mut resp = Response::new()
+        .add_attribute("action", "reveal_bid")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("bid", bid);
+
+    let mut auction = SEALED_AUCTIONS
+        .may_load(deps.storage, option_id)?
+        .unwrap_or_default();
+    if bid >= call.strike && (call.bidder.is_none() || bid > call.bid) {
+        if call.bidder.is_some() {
+            auction.second_bid = call.bid;
+        }
+        call.bid = bid;
+        call.bidder = Some(info.sender.clone());
+        call.save(deps.storage, option_id)?;
+        sync_option_metadata(deps.storage, option_id, &call, config)?;
+
+        // the high bidder becomes the beneficial owner of the asset, like in open auctions
+        resp = resp.add_message(set_beneficial_owner_wasm_msg(
+            call.vault_addr.as_str(),
+            call.asset_id.as_str(),
+            info.sender.as_str(),
+        )?);
+    } else if bid > auction.second_bid {
+        auction.second_bid = bid;
+    }
+    SEALED_AUCTIONS.save(deps.storage, option_id, &auction)?;
+
+    Ok(resp)
+}
+
+/// Permissionlessly releases the deposit of a sealed bid which can no longer win the auction.
+/// Unrevealed deposits are refunded or forfeited to the writer once the reveal period is over.
+pub(crate) fn release_deposit(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    bidder: String,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let AuctionMode::SealedBid {
+        reveal_period,
+        unrevealed_deposits,
+        ..
+    } = &config.auction_mode
+    else {
+        return Err(ContractError::OpenAuction {});
+    };
+
+    let bidder = deps.api.addr_validate(&bidder)?;
+    let call = CallInstrument::load(deps.storage, option_id)?;
+    let commitment = BID_COMMITMENTS
+        .may_load(deps.storage, (*option_id, &bidder))?
+        .ok_or(ContractError::NoCommittedBid {})?;
+
+    ensure!(
+        call.bidder.as_ref() != Some(&bidder),
+        ContractError::WinningDepositLocked {}
+    );
+
+    // a revealed bid which is not the high bid can't win anymore
+    let receiver = match commitment.revealed_bid {
+        Some(_) => bidder.clone(),
+        None => {
+            ensure!(
+                call.settled || reveal_end(&call.expiration, *reveal_period).is_expired(&env.block),
+                ContractError::RevealPeriodNotOver {}
+            );
+            let cancelled = SEALED_AUCTIONS
+                .may_load(deps.storage, option_id)?
+                .unwrap_or_default()
+                .cancelled;
+            match unrevealed_deposits {
+                UnrevealedDeposits::ForfeitToWriter if !cancelled => call.writer_addr.clone(),
+                _ => bidder.clone(),
+            }
+        }
+    };
+    BID_COMMITMENTS.remove(deps.storage, (*option_id, &bidder));
+
+    let resp = if receiver == bidder {
+        Response::new().add_submessage(refund_msg(
+            deps.storage,
+            config,
+            receiver.clone(),
+            commitment.deposit,
+        )?)
+    } else {
+        Response::new().add_message(config.allowed_denom.send(&receiver, &commitment.deposit)?)
+    };
+
+    Ok(resp
+        .add_attribute("action", "release_deposit")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("bidder", bidder)
+        .add_attribute("receiver", receiver)
+        .add_attribute("amount", commitment.deposit))
+}
+
+/// Closes the sealed bid auction of an option once the reveal period is over. The winner pays their bid,
+/// or the second price, and the rest of their deposit is refunded.
+fn close_sealed_auction(
+    storage: &mut dyn Storage,
+    env: &Env,
+    call: &mut CallInstrument,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Option<SubMsg>, ContractError> {
+    let AuctionMode::SealedBid {
+        reveal_period,
+        second_price,
+        ..
+    } = config.auction_mode
+    else {
+        return Ok(None);
+    };
+
+    ensure!(
+        reveal_end(&call.expiration, reveal_period).is_expired(&env.block),
+        ContractError::RevealPeriodNotOver {}
+    );
+
+    let winner = call
+        .bidder
+        .clone()
+        .ok_or(ContractError::NoWinningBidder {})?;
+    let commitment = BID_COMMITMENTS.load(storage, (*option_id, &winner))?;
+    BID_COMMITMENTS.remove(storage, (*option_id, &winner));
+
+    if second_price {
+        let auction = SEALED_AUCTIONS
+            .may_load(storage, option_id)?
+            .unwrap_or_default();
+        call.bid = auction.second_bid.max(call.strike);
+    }
+
+    // the writer only escrowed the spread over the strike
+    let paid = if winner == call.writer_addr {
+        call.bid.checked_sub(call.strike)?
+    } else {
+        call.bid
+    };
+    let excess = commitment.deposit.checked_sub(paid)?;
+    if excess.is_zero() {
+        return Ok(None);
+    }
+
+    Ok(Some(refund_msg(storage, config, winner, excess)?))
+}
+
+/// Marks the sealed bid auction of an option settled before expiration as cancelled,
+/// so unrevealed deposits are always refunded.
+fn cancel_sealed_auction(
+    storage: &mut dyn Storage,
+    option_id: &OptionId,
+    config: &Config,
+) -> StdResult<()> {
+    if let AuctionMode::SealedBid { .. } = config.auction_mode {
+        SEALED_AUCTIONS.update(storage, option_id, |auction| -> StdResult<_> {
+            Ok(SealedAuction {
+                cancelled: true,
+                ..auction.unwrap_or_default()
+            })
+        })?;
+    }
+    Ok(())
+}
+
+/// Exercise an American style option with native funds attached to the message.
+pub(crate) fn exercise(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    let payment = find_allowed_coin(info.funds.clone(), &config.allowed_denom)
+        .ok_or(ContractError::DenomNotAllowed {})?;
+
+    exercise_option(deps, env, info.sender, payment.amount, option_id, config)
+}
+
+/// The holder pays the strike to the writer and receives the underlying asset from the vault.
+/// An outstanding bid is refunded and the option NFT is burned.
+fn exercise_option(
+    deps: DepsMut,
+    env: &Env,
+    holder: Addr,
+    amount: Uint128,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    let mut call = CallInstrument::load(deps.storage, option_id)?;
+
+    ensure!(
+        call.exercise_style == ExerciseStyle::American,
+        ContractError::NotAmericanOption {}
+    );
+    ensure!(
+        !call.settled,
+        ContractError::OptionAlreadySettled(option_id.to_owned())
+    );
+    ensure!(
+        !call.expiration.is_expired(&env.block),
+        ContractError::OptionIsExpired {}
+    );
+
+    let owner_option = option_owner(&deps, env, option_id.to_string())?;
+    ensure!(
+        holder == owner_option,
+        ContractError::OnlyOptionOwner(holder.to_string())
+    );
+    ensure!(
+        amount == call.strike,
+        ContractError::InvalidStrikeDeposit {}
+    );
+
+    // return current bidder's money
+    let mut refunds = vec![];
+    let mut events = vec![];
+    if let Some(high_bidder) = call.bidder.take() {
         let returned_amount = if high_bidder == call.writer_addr {
             call.bid.checked_sub(call.strike)?
         } else {
             call.bid
         };
-        msgs.push(bank_send_msg(
-            high_bidder.into_string(),
-            config.allowed_denom.coins(&returned_amount),
-        ))
+        if !returned_amount.is_zero() {
+            refunds.push(refund_msg(
+                deps.storage,
+                config,
+                high_bidder.clone(),
+                returned_amount,
+            )?);
+            events.push(
+                BidRefunded {
+                    option_id: *option_id,
+                    bidder: high_bidder,
+                    amount: returned_amount,
+                    denom: config.allowed_denom.clone(),
+                }
+                .into(),
+            );
+        }
+    }
+
+    // settle the option
+    call.settled = true;
+    call.save(deps.storage, option_id)?;
+    cancel_sealed_auction(deps.storage, option_id, config)?;
+    events.extend(remove_listing(deps.storage, option_id, config)?);
+    return_burn_bounty(deps.storage, option_id, &call.writer_addr, config)?;
+
+    CallInstrumentContract::default()
+        .tokens
+        .remove(deps.storage, &option_id.to_string())?;
+    CallInstrumentContract::default().decrement_tokens(deps.storage)?;
+
+    // the holder becomes the owner of the asset and receives it, the writer receives the strike
+    let msgs = vec![
+        set_beneficial_owner_wasm_msg(
+            call.vault_addr.as_str(),
+            call.asset_id.as_str(),
+            holder.as_str(),
+        )?,
+        clear_entitlement_and_distribute_wasm_msg(
+            call.vault_addr.as_str(),
+            call.asset_id.as_str(),
+            holder.as_str(),
+        )?,
+        config.allowed_denom.send(&call.writer_addr, &call.strike)?,
+    ];
+
+    events.push(
+        OptionExercised {
+            option_id: *option_id,
+            holder: holder.clone(),
+            strike: call.strike,
+            denom: config.allowed_denom.clone(),
+        }
+        .into(),
+    );
+
+    Ok(Response::new()
+        .add_submessages(refunds)
+        .add_messages(msgs)
+        .add_events(events)
+        .add_attribute("action", "exercise")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("holder", holder))
+}
+
+/// Allows the writer to reclaim an entitled asset. This is only possible
+/// when the writer holds the option nft and calls this function.
+pub(crate) fn reclaim_asset(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    withdraw: bool,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?; // TODO ???
+
+    let mut call = CallInstrument::load(deps.storage, option_id)?;
+
+    ensure!(
+        call.writer_addr == info.sender,
+        ContractError::Unauthorized {} // TODO change type error
+    );
+    ensure!(
+        !call.settled,
+        ContractError::OptionAlreadySettled(option_id.to_owned())
+    );
+    ensure!(
+        !call.expiration.is_expired(&env.block),
+        ContractError::OptionIsExpired {}
+    );
+
+    let owner_option = option_owner(&deps, &env, option_id.to_string())?;
+    ensure!(
+        call.writer_addr.to_owned().into_string() == owner_option,
+        ContractError::WriterMustOwnOption {}
+    );
+
+    // settle the option
+    call.settled = true;
+    call.save(deps.storage, option_id)?;
+    cancel_sealed_auction(deps.storage, option_id, config)?;
+
+    // return current bidder's money
+    let mut refunds = vec![];
+    let mut events = vec![];
+    if let Some(high_bidder) = call.bidder {
+        let returned_amount = if high_bidder == call.writer_addr {
+            call.bid.checked_sub(call.strike)?
+        } else {
+            call.bid
+        };
+        if !returned_amount.is_zero() {
+            refunds.push(refund_msg(
+                deps.storage,
+                config,
+                high_bidder.clone(),
+                returned_amount,
+            )?);
+            events.push(
+                BidRefunded {
+                    option_id: *option_id,
+                    bidder: high_bidder,
+                    amount: returned_amount,
+                    denom: config.allowed_denom.clone(),
+                }
+                .into(),
+            );
+        }
+    }
+    events.extend(remove_listing(deps.storage, option_id, config)?);
+    return_burn_bounty(deps.storage, option_id, &call.writer_addr, config)?;
+
+    // burn the option NFT
+    burn_option_nft(deps, env, info, option_id.to_string())?;
+
+    let mut msgs = vec![];
+
+    // if we have a bid, we may have set the bidder, so make sure to revert it here.
+    msgs.push(set_beneficial_owner_wasm_msg(
+        call.vault_addr.as_str(),
+        call.asset_id.as_str(),
+        call.writer_addr.as_str(),
+    )?);
+
+    if withdraw {
+        msgs.push(clear_entitlement_and_distribute_wasm_msg(
+            call.vault_addr.as_str(),
+            call.asset_id.as_str(),
+            call.writer_addr.as_str(),
+        )?)
+    } else {
+        msgs.push(clear_entitlement_wasm_msg(
+            call.vault_addr.as_str(),
+            call.asset_id.as_str(),
+        )?)
+    }
+
+    events.push(
+        AssetReclaimed {
+            option_id: *option_id,
+            writer: call.writer_addr,
+            withdrawn: withdraw,
+        }
+        .into(),
+    );
+
+    Ok(Response::new()
+        .add_submessages(refunds)
+        .add_messages(msgs)
+        .add_events(events)
+        .add_attribute("action", "reclaim_asset")
+        .add_attribute("option_id", option_id.to_string()))
+}
+
+/// Allows the writer to change the terms of an option without bids while they hold the option nft.
+/// The entitlement is cleared and re-imposed with the new expiration in the same transaction.
+/// A listing of the option is cancelled, as it was made on the previous terms.
+pub(crate) fn amend_option(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    new_strike: Uint128,
+    new_expiration: Expiration,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let mut call = load_amendable_option(&deps, env, &info.sender, option_id)?;
+
+    config.check_expiration(&new_expiration, &env.block)?;
+
+    call.strike = new_strike;
+    call.expiration = new_expiration;
+    call.auction_end = new_expiration;
+    call.save(deps.storage, option_id)?;
+    sync_option_metadata(deps.storage, option_id, &call, config)?;
+    let listing_events = remove_listing(deps.storage, option_id, config)?;
+
+    let msgs = reimpose_entitlement_msgs(env, &call, config)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_events(listing_events)
+        .add_event(
+            OptionAmended {
+                option_id: *option_id,
+                strike: new_strike,
+                expiration: new_expiration,
+            }
+            .into(),
+        )
+        .add_attribute("action", "amend_option")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("strike", new_strike)
+        .add_attribute("expiration", new_expiration.to_string()))
+}
+
+/// Allows the writer to settle an option without bids while they hold the option nft
+/// and mint a new option with the new strike and expiration on the same asset.
+/// The new option keeps the settlement terms of the rolled option.
+pub(crate) fn roll_option(
+    mut deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    new_strike: Uint128,
+    new_expiration: Expiration,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let mut call = load_amendable_option(&deps, env, &info.sender, option_id)?;
+
+    // settle the option and burn its nft, so a new option can be minted on the asset
+    call.settled = true;
+    call.save(deps.storage, option_id)?;
+    let listing_events = remove_listing(deps.storage, option_id, config)?;
+    return_burn_bounty(deps.storage, option_id, &call.writer_addr, config)?;
+
+    CallInstrumentContract::default()
+        .tokens
+        .remove(deps.storage, &option_id.to_string())?;
+    CallInstrumentContract::default().decrement_tokens(deps.storage)?;
+
+    let new_option_id = mint_call(
+        deps.branch(),
+        env,
+        &call.writer_addr,
+        &call.vault_addr,
+        &call.asset_id,
+        new_strike,
+        new_expiration,
+        call.exercise_style.clone(),
+        config,
+    )?;
+
+    call.expiration = new_expiration;
+    call.auction_end = new_expiration;
+    let msgs = reimpose_entitlement_msgs(env, &call, config)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_events(listing_events)
+        .add_event(option_minted_event(deps.storage, &new_option_id, config)?)
+        .add_attribute("action", "roll_option")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("new_option_id", new_option_id.to_string()))
+}
+
+/// Loads an option which can be amended or rolled: it has no bids, open or committed, is neither settled
+/// nor expired, and the writer still holds its nft.
+fn load_amendable_option(
+    deps: &DepsMut,
+    env: &Env,
+    sender: &Addr,
+    option_id: &OptionId,
+) -> Result<CallInstrument, ContractError> {
+    let call = CallInstrument::load(deps.storage, option_id)?;
+
+    ensure!(call.writer_addr == *sender, ContractError::Unauthorized {});
+    ensure!(
+        !call.settled,
+        ContractError::OptionAlreadySettled(option_id.to_owned())
+    );
+    ensure!(
+        !call.expiration.is_expired(&env.block),
+        ContractError::OptionIsExpired {}
+    );
+    ensure!(call.bidder.is_none(), ContractError::OptionHasBids {});
+    // sealed bids were committed against the current terms, and their deposits are held until released
+    ensure!(
+        BID_COMMITMENTS
+            .prefix(*option_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_none(),
+        ContractError::OptionHasBids {}
+    );
+
+    let owner_option = option_owner(deps, env, option_id.to_string())?;
+    ensure!(
+        call.writer_addr == owner_option,
+        ContractError::WriterMustOwnOption {}
+    );
+
+    Ok(call)
+}
+
+/// Replaces the entitlement of the option's asset with one that expires with the settlement auction.
+fn reimpose_entitlement_msgs(
+    env: &Env,
+    call: &CallInstrument,
+    config: &Config,
+) -> StdResult<Vec<CosmosMsg>> {
+    Ok(vec![
+        clear_entitlement_wasm_msg(call.vault_addr.as_str(), call.asset_id.as_str())?,
+        impose_entitlement_wasm_msg(
+            call.vault_addr.as_str(),
+            call.asset_id.as_str(),
+            env.contract.address.as_str(),
+            entitlement_expiration(&call.auction_end, config),
+        )?,
+    ])
+}
+
+/// Permissionlessly settle an expired option when the option expires in the money,
+/// distributing the proceeds to the Writer, Holder, and Bidder
+/// WRITER (who originally called mint() and owned underlying asset) receives the `strike`
+/// HOLDER (ownerOf(optionId)) receives `bid - strike`
+/// HIGH BIDDER (call.bidder) that pays `bid`, becomes ownerOf NFT,
+pub(crate) fn settle_option(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    let mut call = CallInstrument::load(deps.storage, option_id)?;
+    let high_bidder_addr = ensure_settleable(&env, &call, option_id, config)?;
+
+    // sealed bid auctions settle at the revealed price once the reveal period is over
+    let deposit_refund = close_sealed_auction(deps.storage, &env, &mut call, option_id, config)?;
+
+    let breakdown = settlement_breakdown(
+        &call,
+        &protocol_config(&deps.querier, config)?,
+        settlement_royalty(&deps.querier, &call, config),
+        config.keeper_bounty,
+    )?;
+
+    let owner_option = option_owner(&deps, &env, option_id.to_string())?;
+
+    // set settled to prevent an additional attempt to settle the option
+    call.settled = true;
+    call.save(deps.storage, option_id)?;
+    sync_option_metadata(deps.storage, option_id, &call, config)?;
+    let listing_events = remove_listing(deps.storage, option_id, config)?;
+    return_burn_bounty(deps.storage, option_id, &call.writer_addr, config)?;
+
+    let mut msgs = vec![];
+
+    // if the option writer is the high bidder they don't receive the strike because they bid on the spread.
+    if high_bidder_addr != call.writer_addr && !breakdown.writer_amount.is_zero() {
+        msgs.push(
+            config
+                .allowed_denom
+                .send(&call.writer_addr, &breakdown.writer_amount)?,
+        );
+    };
+
+    let fees = breakdown.protocol_fee + breakdown.strike_fee;
+    if let Some(fee_recipient) = &breakdown.fee_recipient {
+        if !fees.is_zero() {
+            msgs.push(config.allowed_denom.send(fee_recipient, &fees)?);
+        }
+    }
+
+    if let Some(royalty_recipient) = &breakdown.royalty_recipient {
+        if !breakdown.royalty_amount.is_zero() {
+            msgs.push(
+                config
+                    .allowed_denom
+                    .send(royalty_recipient, &breakdown.royalty_amount)?,
+            );
+        }
+    }
+
+    // whoever settles the option is rewarded, so expired options don't wait for the holder
+    if !breakdown.keeper_bounty.is_zero() {
+        msgs.push(
+            config
+                .allowed_denom
+                .send(&info.sender, &breakdown.keeper_bounty)?,
+        );
+    }
+    let keeper = info.sender.clone();
+
+    let mut claimable = false;
+    if info.sender == owner_option {
+        // send option holder their earnings
+        if !breakdown.holder_amount.is_zero() {
+            msgs.push(
+                config
+                    .allowed_denom
+                    .send(owner_option, &breakdown.holder_amount)?,
+            );
+        }
+        burn_option_nft(deps, env, info, option_id.to_string())?;
+    } else if breakdown.holder_amount.is_zero() {
+        // nothing is left for the holder to claim, the option is burned right away
+        let holder = MessageInfo {
+            sender: Addr::unchecked(owner_option),
+            funds: vec![],
+        };
+        burn_option_nft(deps, env, holder, option_id.to_string())?;
+    } else {
+        OPTION_CLAIMS.save(deps.storage, option_id, &breakdown.holder_amount)?;
+        claimable = true;
+    }
+
+    let settled = OptionSettled {
+        option_id: *option_id,
+        bidder: high_bidder_addr,
+        bid: call.bid,
+        denom: config.allowed_denom.clone(),
+        writer_amount: breakdown.writer_amount,
+        holder_amount: breakdown.holder_amount,
+        protocol_fee: breakdown.protocol_fee,
+        strike_fee: breakdown.strike_fee,
+        royalty_amount: breakdown.royalty_amount,
+        keeper,
+        keeper_bounty: breakdown.keeper_bounty,
+        claimable,
+    };
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_submessages(deposit_refund)
+        .add_events(listing_events)
+        .add_event(settled.into())
+        .add_attribute("action", "settle_option")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("claimable", claimable.to_string())
+        .add_attribute("writer_amount", breakdown.writer_amount)
+        .add_attribute("holder_amount", breakdown.holder_amount)
+        .add_attribute("protocol_fee", breakdown.protocol_fee)
+        .add_attribute("strike_fee", breakdown.strike_fee)
+        .add_attribute("royalty_amount", breakdown.royalty_amount)
+        .add_attribute("keeper_bounty", breakdown.keeper_bounty))
+}
+
+/// Checks that an option can be settled, returning its winning bidder.
+fn ensure_settleable(
+    env: &Env,
+    call: &CallInstrument,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Addr, ContractError> {
+    let high_bidder_addr = call
+        .bidder
+        .clone()
+        .ok_or(ContractError::NoWinningBidder {})?;
+    ensure!(
+        call.auction_end.is_expired(&env.block),
+        ContractError::OptionNotExpired(option_id.to_owned())
+    );
+    ensure!(
+        !call.settled,
+        ContractError::OptionAlreadySettled(option_id.to_owned())
+    );
+    if let AuctionMode::SealedBid { reveal_period, .. } = config.auction_mode {
+        ensure!(
+            reveal_end(&call.expiration, reveal_period).is_expired(&env.block),
+            ContractError::RevealPeriodNotOver {}
+        );
     }
 
-    // if we have a bid, we may have set the bidder, so make sure to revert it here.
-    msgs.push(set_beneficial_owner_wasm_msg(
-        call.vault_addr.as_str(),
-        call.asset_id.as_str(),
-        call.writer_addr.as_str(),
-    )?);
+    Ok(high_bidder_addr)
+}
 
-    if withdraw {
-        msgs.push(clear_entitlement_and_distribute_wasm_msg(
-            call.vault_addr.as_str(),
-            call.asset_id.as_str(),
-            call.writer_addr.as_str(),
-        )?)
-    } else {
-        msgs.push(clear_entitlement_wasm_msg(
-            call.vault_addr.as_str(),
-            call.asset_id.as_str(),
-        )?)
+/// Settles each of the given options, so keepers can settle many expired options in one transaction.
+/// Each option is settled under the terms it was written under. Unknown options, options already settled,
+/// or which can't be settled yet, are skipped so they don't revert the rest of the batch.
+pub(crate) fn settle_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    option_ids: Vec<OptionId>,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    ensure_batch_size(&option_ids)?;
+
+    let mut resp = Response::new();
+    let mut settled_ids = vec![];
+    let mut skipped_ids = vec![];
+    for option_id in &option_ids {
+        let option_config = config.for_option(deps.storage, option_id)?;
+        let settleable = CallInstrument::load(deps.storage, option_id)
+            .map_err(ContractError::from)
+            .and_then(|call| ensure_settleable(&env, &call, option_id, &option_config));
+        if settleable.is_err() {
+            skipped_ids.push(*option_id);
+            continue;
+        }
+
+        let settled = settle_option(
+            deps.branch(),
+            env.clone(),
+            info.clone(),
+            option_id,
+            &option_config,
+        )?;
+        resp = resp
+            .add_submessages(settled.messages)
+            .add_events(settled.events)
+            .add_attributes(settled.attributes);
+        settled_ids.push(*option_id);
     }
 
-    Ok(Response::new().add_messages(msgs))
+    Ok(resp
+        .add_attribute("action", "settle_batch")
+        .add_attribute("option_ids", join_option_ids(&settled_ids))
+        .add_attribute("skipped_option_ids", join_option_ids(&skipped_ids)))
 }
 
-/// Permissionlessly settle an expired option when the option expires in the money,
-/// distributing the proceeds to the Writer, Holder, and Bidder
-/// WRITER (who originally called mint() and owned underlying asset) receives the `strike`
-/// HOLDER (ownerOf(optionId)) receives `bid - strike`
-/// HIGH BIDDER (call.bidder) that pays `bid`, becomes ownerOf NFT,
-pub(crate) fn settle_option(
+/// Allows anyone to burn the instrument NFT for an expired option without bids.
+/// The option is settled, so a new option can be written on the asset. An entitlement the instrument
+/// still holds is released, and the writer can also withdraw the asset with it.
+pub(crate) fn burn_expired_option(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     option_id: &OptionId,
+    withdraw: bool,
     config: &Config,
 ) -> Result<Response, ContractError> {
     let mut call = CallInstrument::load(deps.storage, option_id)?;
 
-    let high_bidder_addr = call
-        .bidder
-        .clone()
-        .ok_or(ContractError::NoWinningBidder())?;
+    // sealed bids can still be revealed after expiration
+    if let AuctionMode::SealedBid { reveal_period, .. } = config.auction_mode {
+        ensure!(
+            reveal_end(&call.expiration, reveal_period).is_expired(&env.block),
+            ContractError::RevealPeriodNotOver {}
+        );
+    }
+
+    ensure!(call.bidder.is_none(), ContractError::OptionHasBids {});
     ensure!(
         call.expiration.is_expired(&env.block),
         ContractError::OptionNotExpired(option_id.to_owned())
@@ -485,71 +1679,134 @@ pub(crate) fn settle_option(
         !call.settled,
         ContractError::OptionAlreadySettled(option_id.to_owned())
     );
+    ensure!(
+        !withdraw || call.writer_addr == info.sender,
+        ContractError::Unauthorized {}
+    );
 
-    let spread = call.bid.checked_sub(call.strike)?;
-
-    let owner_option = option_owner(&deps, &env, option_id.to_string())?;
-
-    // set settled to prevent an additional attempt to settle the option
+    // settle the option, so a new option can be written on the asset
     call.settled = true;
     call.save(deps.storage, option_id)?;
+    let listing_events = remove_listing(deps.storage, option_id, config)?;
 
+    // without bids the writer is still the beneficial owner. The entitlement usually lapses with the option,
+    // the asset is then already free and the writer may have withdrawn it, so only an entitlement
+    // the instrument still holds is cleared and the burn never depends on the vault.
     let mut msgs = vec![];
+    let mut withdrawn = false;
+    if is_entitled_operator(&deps.querier, &env, &call.vault_addr, &call.asset_id) {
+        if withdraw {
+            msgs.push(clear_entitlement_and_distribute_wasm_msg(
+                call.vault_addr.as_str(),
+                call.asset_id.as_str(),
+                call.writer_addr.as_str(),
+            )?);
+            withdrawn = true;
+        } else {
+            msgs.push(clear_entitlement_wasm_msg(
+                call.vault_addr.as_str(),
+                call.asset_id.as_str(),
+            )?);
+        }
+    }
 
-    // if the option writer is the high bidder they don't receive the strike because they bid on the spread.
-    if high_bidder_addr != call.writer_addr {
-        msgs.push(bank_send_msg(
-            call.writer_addr.into_string(),
-            config.allowed_denom.coins(&call.strike),
-        ));
-    };
-
-    let mut claimable = false;
-    if info.sender == owner_option {
-        // send option holder their earnings
-        msgs.push(bank_send_msg(
-            owner_option,
-            config.allowed_denom.coins(&spread),
-        ));
-        burn_option_nft(deps, env, info, option_id.to_string())?;
-    } else {
-        OPTION_CLAIMS.save(deps.storage, option_id, &spread)?;
-        claimable = true;
+    // the caller is paid the bounty the writer funded, an option without bids has no spread to pay it from
+    let keeper = info.sender.clone();
+    let burn_bounty = BURN_BOUNTIES
+        .may_load(deps.storage, option_id)?
+        .unwrap_or_default();
+    BURN_BOUNTIES.remove(deps.storage, option_id);
+    if !burn_bounty.is_zero() {
+        msgs.push(config.allowed_denom.send(&keeper, &burn_bounty)?);
     }
 
+    // the caller is usually not the holder, so the nft is removed without the cw721 ownership checks
+    CallInstrumentContract::default()
+        .tokens
+        .remove(deps.storage, &option_id.to_string())?;
+    CallInstrumentContract::default().decrement_tokens(deps.storage)?;
+
     Ok(Response::new()
         .add_messages(msgs)
-        .add_attribute("action", "settle_option")
-        .add_attribute("claimable", claimable.to_string()))
+        .add_events(listing_events)
+        .add_event(
+            ExpiredOptionBurned {
+                option_id: *option_id,
+                keeper,
+                burn_bounty,
+                withdrawn,
+            }
+            .into(),
+        )
+        .add_attribute("action", "burn_expired_option")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("burn_bounty", burn_bounty))
 }
 
-/// Allows anyone to burn the instrument NFT for an expired option
-pub(crate) fn burn_expired_option(
+/// Funds the burn bounty of an option with native funds attached to the message.
+pub(crate) fn fund_burn_bounty(
     deps: DepsMut,
-    env: Env,
     info: MessageInfo,
     option_id: &OptionId,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    let payment = find_allowed_coin(info.funds.clone(), &config.allowed_denom)
+        .ok_or(ContractError::DenomNotAllowed {})?;
+
+    add_burn_bounty(deps, info.sender, payment.amount, option_id, config)
+}
+
+/// The writer adds to the bounty paid for burning their option once it expired without bids.
+fn add_burn_bounty(
+    deps: DepsMut,
+    writer: Addr,
+    amount: Uint128,
+    option_id: &OptionId,
+    config: &Config,
 ) -> Result<Response, ContractError> {
     let call = CallInstrument::load(deps.storage, option_id)?;
 
-    ensure!(
-        call.bidder.is_none(),
-        StdError::generic_err("option has bids")
-    );
-    ensure!(
-        call.expiration.is_expired(&env.block),
-        ContractError::OptionNotExpired(option_id.to_owned())
-    );
+    ensure!(call.writer_addr == writer, ContractError::Unauthorized {});
     ensure!(
         !call.settled,
         ContractError::OptionAlreadySettled(option_id.to_owned())
     );
+    ensure!(!amount.is_zero(), ContractError::ZeroBurnBounty {});
 
-    call.update(deps.storage, option_id)?;
+    let burn_bounty = BURN_BOUNTIES
+        .may_load(deps.storage, option_id)?
+        .unwrap_or_default()
+        .checked_add(amount)?;
+    BURN_BOUNTIES.save(deps.storage, option_id, &burn_bounty)?;
 
-    burn_option_nft(deps, env, info, option_id.to_string())?;
+    Ok(Response::new()
+        .add_event(
+            BurnBountyFunded {
+                option_id: *option_id,
+                writer,
+                amount,
+                denom: config.allowed_denom.clone(),
+                burn_bounty,
+            }
+            .into(),
+        )
+        .add_attribute("action", "fund_burn_bounty")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("burn_bounty", burn_bounty))
+}
 
-    Ok(Response::new().add_attribute("action", "burn_expired_option"))
+/// Returns the burn bounty of an option which ended without being burned to the pending refunds of the writer.
+fn return_burn_bounty(
+    storage: &mut dyn Storage,
+    option_id: &OptionId,
+    writer: &Addr,
+    config: &Config,
+) -> StdResult<()> {
+    let Some(burn_bounty) = BURN_BOUNTIES.may_load(storage, option_id)? else {
+        return Ok(());
+    };
+    BURN_BOUNTIES.remove(storage, option_id);
+    credit_pending_refund(storage, writer, config.allowed_denom.clone(), burn_bounty)
 }
 
 /// Allows the option owner to claim proceeds if the option was settled
@@ -569,13 +1826,530 @@ pub(crate) fn claim_option_proceeds(
     let claim = OPTION_CLAIMS.load(deps.storage, option_id)?;
     OPTION_CLAIMS.remove(deps.storage, option_id);
 
-    ensure!(!claim.is_zero(), StdError::generic_err("claim is zero"));
+    ensure!(!claim.is_zero(), ContractError::ZeroClaim {});
     burn_option_nft(deps, env, info, option_id.to_string())?;
 
-    let send_claim_msg = bank_send_msg(owner_option, config.allowed_denom.coins(&claim));
+    let send_claim_msg = config.allowed_denom.send(&owner_option, &claim)?;
     Ok(Response::new()
         .add_message(send_claim_msg)
-        .add_attribute("action", "claim_option_proceeds"))
+        .add_event(
+            ProceedsClaimed {
+                option_id: *option_id,
+                holder: Addr::unchecked(owner_option),
+                amount: claim,
+                denom: config.allowed_denom.clone(),
+            }
+            .into(),
+        )
+        .add_attribute("action", "claim_option_proceeds")
+        .add_attribute("option_id", option_id.to_string()))
+}
+
+/// Lists an option for sale by its holder. A listing of the holder without bids, or a listing
+/// left by a previous holder, is replaced by the new one.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn list_option(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    premium: Uint128,
+    expires: Expiration,
+    kind: ListingKind,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let call = CallInstrument::load(deps.storage, option_id)?;
+    ensure!(
+        !call.settled,
+        ContractError::OptionAlreadySettled(option_id.to_owned())
+    );
+    ensure!(
+        !call.expiration.is_expired(&env.block),
+        ContractError::OptionIsExpired {}
+    );
+
+    let owner_option = option_owner(&deps, env, option_id.to_string())?;
+    ensure!(
+        info.sender == owner_option,
+        ContractError::OnlyOptionOwner(info.sender.to_string())
+    );
+    ensure!(!premium.is_zero(), ContractError::ZeroPremium {});
+
+    // the listing must end before the option expires
+    ensure!(
+        !expires.is_expired(&env.block) && expires <= call.expiration,
+        ContractError::InvalidExpiry {}
+    );
+
+    let mut events = vec![];
+    if let Some(listing) = LISTINGS.may_load(deps.storage, option_id)? {
+        ensure!(
+            listing.seller != info.sender || listing.bidder.is_none(),
+            ContractError::ListingHasBids {}
+        );
+        events.extend(remove_listing(deps.storage, option_id, config)?);
+    }
+
+    LISTINGS.save(
+        deps.storage,
+        option_id,
+        &Listing {
+            seller: info.sender.clone(),
+            premium,
+            expires,
+            kind: kind.clone(),
+            bid: Uint128::zero(),
+            bidder: None,
+        },
+    )?;
+
+    events.push(
+        OptionListed {
+            option_id: *option_id,
+            seller: info.sender.clone(),
+            premium,
+            denom: config.allowed_denom.clone(),
+            expires,
+            auction: kind == ListingKind::Auction,
+        }
+        .into(),
+    );
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("action", "list_option")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("seller", info.sender)
+        .add_attribute("premium", premium))
+}
+
+/// Allows the seller to cancel a listing, as long as no buyer has bid on it.
+pub(crate) fn cancel_listing(
+    deps: DepsMut,
+    info: MessageInfo,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let listing = LISTINGS
+        .may_load(deps.storage, option_id)?
+        .ok_or(ContractError::ListingNotFound(*option_id))?;
+    ensure!(
+        listing.seller == info.sender,
+        ContractError::Unauthorized {}
+    );
+    ensure!(listing.bidder.is_none(), ContractError::ListingHasBids {});
+
+    let events = remove_listing(deps.storage, option_id, config)?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("action", "cancel_listing")
+        .add_attribute("option_id", option_id.to_string()))
+}
+
+/// Buy a listed option with native funds attached to the message.
+pub(crate) fn buy_option(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    let payment = find_allowed_coin(info.funds.clone(), &config.allowed_denom)
+        .ok_or(ContractError::DenomNotAllowed {})?;
+
+    purchase_option(deps, env, info.sender, payment.amount, option_id, config)
+}
+
+/// Sells a fixed price listing to the buyer, or places the payment as the best bid of a premium auction.
+/// The outbid bidder of an auction is refunded.
+fn purchase_option(
+    deps: DepsMut,
+    env: &Env,
+    buyer: Addr,
+    amount: Uint128,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    let mut listing = LISTINGS
+        .may_load(deps.storage, option_id)?
+        .ok_or(ContractError::ListingNotFound(*option_id))?;
+    ensure!(
+        !listing.expires.is_expired(&env.block),
+        ContractError::ListingExpired {}
+    );
+
+    // the nft is not escrowed, a listing is stale once the seller transferred the option
+    let owner_option = option_owner(&deps, env, option_id.to_string())?;
+    ensure!(
+        listing.seller == owner_option,
+        ContractError::SellerNotOwner {}
+    );
+
+    let resp = Response::new().add_attribute("option_id", option_id.to_string());
+
+    match listing.kind {
+        ListingKind::FixedPrice => {
+            ensure!(
+                amount == listing.premium,
+                ContractError::InvalidPremium {
+                    premium: listing.premium
+                }
+            );
+            LISTINGS.remove(deps.storage, option_id);
+
+            let (msg, event) = sell_option(
+                deps,
+                env,
+                option_id,
+                &listing.seller,
+                &buyer,
+                listing.premium,
+                config,
+            )?;
+
+            Ok(resp
+                .add_message(msg)
+                .add_event(event)
+                .add_attribute("action", "buy_option")
+                .add_attribute("buyer", buyer))
+        }
+        ListingKind::Auction => {
+            let min_premium = match listing.bidder {
+                Some(_) => listing.bid.checked_add(
+                    listing
+                        .bid
+                        .multiply_ratio(config.min_bid_increment_bps, 10_000u128)
+                        .max(Uint128::one()),
+                )?,
+                None => listing.premium,
+            };
+            ensure!(
+                amount >= min_premium,
+                ContractError::PremiumTooLow { min_premium }
+            );
+
+            // return the escrowed bid to the previous bidder
+            let mut resp = resp;
+            if let Some(outbid) = listing.bidder.take() {
+                resp = resp
+                    .add_submessage(refund_msg(
+                        deps.storage,
+                        config,
+                        outbid.clone(),
+                        listing.bid,
+                    )?)
+                    .add_event(
+                        ListingBidRefunded {
+                            option_id: *option_id,
+                            bidder: outbid,
+                            amount: listing.bid,
+                            denom: config.allowed_denom.clone(),
+                        }
+                        .into(),
+                    );
+            }
+
+            listing.bid = amount;
+            listing.bidder = Some(buyer.clone());
+            LISTINGS.save(deps.storage, option_id, &listing)?;
+
+            Ok(resp
+                .add_event(
+                    ListingBidPlaced {
+                        option_id: *option_id,
+                        bidder: buyer.clone(),
+                        amount,
+                        denom: config.allowed_denom.clone(),
+                    }
+                    .into(),
+                )
+                .add_attribute("action", "bid_listing")
+                .add_attribute("bidder", buyer)
+                .add_attribute("amount", amount))
+        }
+    }
+}
+
+/// Permissionlessly closes an expired premium auction. The option is sold to the best bidder
+/// if the seller still holds it, otherwise the bid is refunded.
+pub(crate) fn finalize_listing(
+    deps: DepsMut,
+    env: Env,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    let listing = LISTINGS
+        .may_load(deps.storage, option_id)?
+        .ok_or(ContractError::ListingNotFound(*option_id))?;
+    ensure!(
+        listing.kind == ListingKind::Auction,
+        ContractError::NotAuctionListing {}
+    );
+    ensure!(
+        listing.expires.is_expired(&env.block),
+        ContractError::ListingNotExpired {}
+    );
+
+    let resp = Response::new()
+        .add_attribute("action", "finalize_listing")
+        .add_attribute("option_id", option_id.to_string());
+
+    let Some(bidder) = listing.bidder.clone() else {
+        let events = remove_listing(deps.storage, option_id, config)?;
+        return Ok(resp.add_events(events));
+    };
+
+    // the option nft may have been transferred or burned since it was listed
+    let seller_holds =
+        option_owner(&deps, &env, option_id.to_string()).is_ok_and(|owner| owner == listing.seller);
+    if !seller_holds {
+        LISTINGS.remove(deps.storage, option_id);
+        return Ok(resp
+            .add_submessage(refund_msg(
+                deps.storage,
+                config,
+                bidder.clone(),
+                listing.bid,
+            )?)
+            .add_event(
+                ListingBidRefunded {
+                    option_id: *option_id,
+                    bidder,
+                    amount: listing.bid,
+                    denom: config.allowed_denom.clone(),
+                }
+                .into(),
+            )
+            .add_event(
+                ListingCancelled {
+                    option_id: *option_id,
+                    seller: listing.seller,
+                }
+                .into(),
+            ));
+    }
+
+    LISTINGS.remove(deps.storage, option_id);
+    let (msg, event) = sell_option(
+        deps,
+        &env,
+        option_id,
+        &listing.seller,
+        &bidder,
+        listing.bid,
+        config,
+    )?;
+
+    Ok(resp
+        .add_message(msg)
+        .add_event(event)
+        .add_attribute("buyer", bidder))
+}
+
+/// Moves the option nft from the seller to the buyer and builds the payment of the premium to the seller.
+fn sell_option(
+    deps: DepsMut,
+    env: &Env,
+    option_id: &OptionId,
+    seller: &Addr,
+    buyer: &Addr,
+    premium: Uint128,
+    config: &Config,
+) -> Result<(CosmosMsg, Event), ContractError> {
+    transfer_option_nft(deps, env, seller, buyer, option_id.to_string())?;
+
+    let sold = OptionSold {
+        option_id: *option_id,
+        seller: seller.clone(),
+        buyer: buyer.clone(),
+        premium,
+        denom: config.allowed_denom.clone(),
+    };
+    Ok((config.allowed_denom.send(seller, &premium)?, sold.into()))
+}
+
+/// Removes the listing of an option that can no longer be sold, because it settled or was cancelled.
+/// The escrowed bid of a premium auction is credited to the pending refunds of the bidder.
+fn remove_listing(
+    storage: &mut dyn Storage,
+    option_id: &OptionId,
+    config: &Config,
+) -> StdResult<Vec<Event>> {
+    let Some(listing) = LISTINGS.may_load(storage, option_id)? else {
+        return Ok(vec![]);
+    };
+    LISTINGS.remove(storage, option_id);
+
+    let mut events = vec![];
+    if let Some(bidder) = listing.bidder {
+        credit_pending_refund(storage, &bidder, config.allowed_denom.clone(), listing.bid)?;
+        events.push(
+            ListingBidRefunded {
+                option_id: *option_id,
+                bidder,
+                amount: listing.bid,
+                denom: config.allowed_denom.clone(),
+            }
+            .into(),
+        );
+    }
+    events.push(
+        ListingCancelled {
+            option_id: *option_id,
+            seller: listing.seller,
+        }
+        .into(),
+    );
+
+    Ok(events)
+}
+
+/// Allows an outbid bidder to withdraw refunds that could not be sent to them.
+pub(crate) fn withdraw_refunds(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let refunds = PENDING_REFUNDS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    ensure!(!refunds.is_empty(), ContractError::NoPendingRefunds {});
+    PENDING_REFUNDS.remove(deps.storage, &info.sender);
+
+    let mut response = Response::new().add_attribute("action", "withdraw_refunds");
+    for (denom, amount) in refunds {
+        response = response
+            .add_message(denom.send(&info.sender, &amount)?)
+            .add_attribute("amount", format!("{}{}", amount, denom));
+    }
+    Ok(response)
+}
+
+/// Removes the replied refund from the refunds in flight,
+/// and credits it to the pending refunds of the bidder when it could not be sent.
+pub(crate) fn refund_replied(
+    deps: DepsMut,
+    reply_id: u64,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    let (bidder, denom, amount) = REFUNDS_IN_FLIGHT
+        .may_load(deps.storage, reply_id)?
+        .ok_or(ContractError::UnknownReplyID {})?;
+    REFUNDS_IN_FLIGHT.remove(deps.storage, reply_id);
+
+    let SubMsgResult::Err(error) = result else {
+        return Ok(Response::new());
+    };
+    credit_pending_refund(deps.storage, &bidder, denom, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "refund_failed")
+        .add_attribute("bidder", bidder)
+        .add_attribute("amount", amount)
+        .add_attribute("error", error))
+}
+
+/// Adds an amount to the refunds in a denom that the bidder can withdraw.
+fn credit_pending_refund(
+    storage: &mut dyn Storage,
+    bidder: &Addr,
+    denom: Denom,
+    amount: Uint128,
+) -> StdResult<()> {
+    let mut refunds = PENDING_REFUNDS
+        .may_load(storage, bidder)?
+        .unwrap_or_default();
+    add_balance(&mut refunds, denom, amount)?;
+    PENDING_REFUNDS.save(storage, bidder, &refunds)
+}
+
+/// Adds an amount to the balance in a denom of a list of balances by denom.
+fn add_balance(
+    balances: &mut Vec<(Denom, Uint128)>,
+    denom: Denom,
+    amount: Uint128,
+) -> StdResult<()> {
+    match balances.iter_mut().find(|(balance, _)| *balance == denom) {
+        Some((_, balance)) => *balance = balance.checked_add(amount)?,
+        None => balances.push((denom, amount)),
+    }
+    Ok(())
+}
+
+/// Builds a refund of a bid, which is credited to the pending refunds of the bidder
+/// instead of failing the whole transaction if the send fails.
+pub(crate) fn refund_msg(
+    storage: &mut dyn Storage,
+    config: &Config,
+    bidder: Addr,
+    amount: Uint128,
+) -> StdResult<SubMsg> {
+    // the reply id is unique among the refunds in flight, so each reply finds its own refund
+    let reply_id = match REFUNDS_IN_FLIGHT
+        .keys(storage, None, None, Order::Descending)
+        .next()
+    {
+        Some(last_id) => last_id? + 1,
+        None => REFUND_REPLY_ID,
+    };
+    REFUNDS_IN_FLIGHT.save(
+        storage,
+        reply_id,
+        &(bidder.clone(), config.allowed_denom.clone(), amount),
+    )?;
+    Ok(SubMsg::reply_always(
+        config.allowed_denom.send(bidder, &amount)?,
+        reply_id,
+    ))
+}
+
+/// Updates the instrument settings. The settlement terms of existing options are not changed.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    expiry_range: Option<ExpiryRange>,
+    expiry_series: Option<ExpirySeries>,
+    clear_expiry_series: bool,
+    allowed_denom: Option<Denom>,
+    min_bid_increment_bps: Option<u64>,
+    settlement_auction_start_offset: Option<u64>,
+    royalties_enabled: Option<bool>,
+    keeper_bounty: Option<Uint128>,
+    mut config: Config,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    assert_protocol_owner(&deps.querier, &config.protocol_addr, &info.sender)?;
+    // a series can't be set and cleared at once
+    ensure!(
+        !clear_expiry_series || expiry_series.is_none(),
+        ContractError::InvalidConfig {}
+    );
+
+    config.expiry_range = expiry_range.unwrap_or(config.expiry_range);
+    if clear_expiry_series {
+        config.expiry_series = None;
+    } else {
+        config.expiry_series = expiry_series.or(config.expiry_series);
+    }
+    config.allowed_denom = allowed_denom.unwrap_or(config.allowed_denom);
+    config.min_bid_increment_bps = min_bid_increment_bps.unwrap_or(config.min_bid_increment_bps);
+    config.settlement_auction_start_offset =
+        settlement_auction_start_offset.unwrap_or(config.settlement_auction_start_offset);
+    config.royalties_enabled = royalties_enabled.unwrap_or(config.royalties_enabled);
+    config.keeper_bounty = keeper_bounty.unwrap_or(config.keeper_bounty);
+    config.validate(deps.api)?;
+    config.save(deps.storage)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender))
 }
 
 pub fn for_test() -> Result<Response, ContractError> {
@@ -1,8 +1,10 @@
 pub mod contract;
 pub mod execute;
+pub mod migrations;
 pub mod msg;
 pub mod query;
 pub mod state;
+pub mod svg;
 pub mod utils;
 
 pub use crate::msg::{
@@ -0,0 +1,130 @@
+//! Storage migrations of the call instrument, run by the `migrate` entry point.
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, Empty, Order, StdError, StdResult, Storage, Uint128};
+use cw721_base::state::TokenInfo;
+use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
+
+use common::{
+    denom::Denom,
+    errors::ContractError,
+    types::{AssetId, ExerciseStyle, ExpiryRange, OptionId},
+};
+
+use crate::{
+    msg::{AuctionMode, OptionMetadata},
+    state::{CallInstrument, Config, OptionTerms, OPTION_TERMS},
+};
+
+/// Storage layout of version 0.1, before settlement auction offsets, cw20 denoms,
+/// exercise styles and on-chain metadata were added.
+pub(crate) mod v0_1 {
+    use super::*;
+
+    #[cw_serde]
+    pub struct Config {
+        pub protocol_addr: Addr,
+        pub allowed_underlying_nft: Addr,
+        pub vault_factory_addr: Addr,
+        pub minimum_option_duration: u64,
+        pub allowed_denom: String,
+        pub min_bid_increment_bps: u64,
+    }
+
+    #[cw_serde]
+    pub struct CallInstrument {
+        pub asset_id: AssetId,
+        pub writer_addr: Addr,
+        pub expiration: Expiration,
+        pub vault_addr: Addr,
+        pub strike: Uint128,
+        pub bid: Uint128,
+        pub bidder: Option<Addr>,
+        pub settled: bool,
+    }
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+    pub const CALL_INSTRUMENTS: Map<OptionId, CallInstrument> = Map::new("call_options");
+    pub const TOKENS: Map<&str, TokenInfo<Empty>> = Map::new("tokens");
+}
+
+/// Option nft storage with the current metadata extension, the owner index is left untouched.
+const TOKENS: Map<&str, TokenInfo<OptionMetadata>> = Map::new("tokens");
+
+/// Migrates the storage of version 0.1. The settlement auctions of existing options start
+/// `settlement_auction_start_offset` seconds before expiration, like the ones of new options.
+/// Existing options are kept even if they exceed `maximum_option_duration`.
+pub(crate) fn migrate_from_v0_1(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    settlement_auction_start_offset: u64,
+    maximum_option_duration: u64,
+) -> Result<(), ContractError> {
+    let legacy = v0_1::CONFIG.load(storage)?;
+    let config = Config {
+        protocol_addr: legacy.protocol_addr,
+        allowed_underlying_nft: legacy.allowed_underlying_nft,
+        vault_factory_addr: legacy.vault_factory_addr,
+        expiry_range: ExpiryRange {
+            min: legacy.minimum_option_duration,
+            max: maximum_option_duration,
+        },
+        expiry_series: None,
+        allowed_denom: Denom::from(legacy.allowed_denom),
+        min_bid_increment_bps: legacy.min_bid_increment_bps,
+        settlement_auction_start_offset,
+        royalties_enabled: false,
+        auction_mode: AuctionMode::Open,
+        anti_snipe: None,
+        keeper_bounty: Uint128::zero(),
+    };
+    config.validate(api)?;
+    config.save(storage)?;
+
+    // options are re-saved through the indexed map, which builds the listing indexes
+    let options = v0_1::CALL_INSTRUMENTS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (option_id, legacy) in options {
+        v0_1::CALL_INSTRUMENTS.remove(storage, option_id);
+        let call = CallInstrument {
+            asset_id: legacy.asset_id,
+            writer_addr: legacy.writer_addr,
+            expiration: legacy.expiration,
+            vault_addr: legacy.vault_addr,
+            strike: legacy.strike,
+            bid: legacy.bid,
+            bidder: legacy.bidder,
+            settled: legacy.settled,
+            exercise_style: ExerciseStyle::European,
+            auction_end: legacy.expiration,
+        };
+        call.save(storage, &option_id)?;
+        OPTION_TERMS.save(storage, &option_id, &OptionTerms::from(&config))?;
+    }
+
+    let tokens = v0_1::TOKENS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (token_id, token) in tokens {
+        let option_id: OptionId = token_id.parse().map_err(|_| {
+            StdError::generic_err(format!(
+                "migrate_from_v0_1 - invalid option id {}",
+                token_id
+            ))
+        })?;
+        let call = CallInstrument::load(storage, &option_id)?;
+        TOKENS.save(
+            storage,
+            &token_id,
+            &TokenInfo {
+                owner: token.owner,
+                approvals: token.approvals,
+                token_uri: token.token_uri,
+                extension: OptionMetadata::new(&call, &config),
+            },
+        )?;
+    }
+
+    Ok(())
+}
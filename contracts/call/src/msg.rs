@@ -1,8 +1,98 @@
-use common::types::{AssetId, OptionId, TokenId};
+use common::{
+    denom::Denom,
+    types::{AssetId, ExerciseStyle, ExpiryRange, ExpirySeries, OptionId, TokenId},
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, CustomMsg, Uint128};
+use cosmwasm_std::{Addr, Binary, CustomMsg, Timestamp, Uint128};
 use cw_utils::Expiration;
 
+use crate::state::{BidCommitment, CallInstrument, Config, Listing, OptionTerms};
+
+/// On-chain metadata of an option nft, returned as the extension of the cw721 `NftInfo` query.
+#[cw_serde]
+pub struct OptionMetadata {
+    /// The collection of the underlying NFT
+    pub underlying_nft: Addr,
+    /// The asset id of the underlying within the vault
+    pub asset_id: AssetId,
+    /// The address of the vault holding the underlying asset
+    pub vault_addr: Addr,
+    /// The strike price to exercise the call option
+    pub strike: Uint128,
+    /// The denom the strike and bids are paid in
+    pub denom: Denom,
+    /// The expiration time of the call option
+    pub expiration: Expiration,
+    pub exercise_style: ExerciseStyle,
+    /// The current high bid in the settlement auction
+    pub bid: Uint128,
+    /// Flag that marks when a settlement action has taken place successfully
+    pub settled: bool,
+}
+
+impl OptionMetadata {
+    pub fn new(call: &CallInstrument, config: &Config) -> Self {
+        Self {
+            underlying_nft: config.allowed_underlying_nft.clone(),
+            asset_id: call.asset_id.clone(),
+            vault_addr: call.vault_addr.clone(),
+            strike: call.strike,
+            denom: config.allowed_denom.clone(),
+            expiration: call.expiration,
+            exercise_style: call.exercise_style.clone(),
+            bid: call.bid,
+            settled: call.settled,
+        }
+    }
+}
+
+/// How the settlement auctions of the instrument's options are run.
+#[cw_serde]
+#[derive(Default)]
+pub enum AuctionMode {
+    /// Open ascending auction, every bid is public and must overbid the current high bid.
+    #[default]
+    Open,
+    /// Bidders commit a hash of their bid along with an escrow deposit during the auction window
+    /// and reveal it within `reveal_period` seconds after expiration. The highest revealed bid wins.
+    SealedBid {
+        reveal_period: u64,
+        /// The winner pays the second highest revealed bid, but at least the strike.
+        second_price: bool,
+        unrevealed_deposits: UnrevealedDeposits,
+    },
+}
+
+/// Extension of an open settlement auction when a bid is placed close to its end.
+#[cw_serde]
+pub struct AntiSnipe {
+    /// Bids placed within this many seconds of the auction end extend the auction
+    pub window: u64,
+    /// Seconds added to the auction end by such a bid
+    pub extension: u64,
+    /// Hard cap in seconds of the total extension past the expiration of the option
+    pub max_extension: u64,
+}
+
+/// How a listed option is sold to buyers.
+#[cw_serde]
+#[derive(Default)]
+pub enum ListingKind {
+    /// The first buyer paying the premium receives the option.
+    #[default]
+    FixedPrice,
+    /// Buyers bid at least the premium while the listing is open, and each bid must beat the best bid
+    /// by the min bid increment. The best bidder receives the option when the listing is finalized.
+    Auction,
+}
+
+/// What happens to the deposit of a sealed bid that was not revealed in time.
+#[cw_serde]
+pub enum UnrevealedDeposits {
+    Refund,
+    ForfeitToWriter,
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// TODO
@@ -15,12 +105,34 @@ pub struct InstantiateMsg {
     pub allowed_underlying_nft: String,
     /// The address of the vault factory.
     pub vault_factory_addr: String,
-    /// Default min duration of an created option
-    pub minimum_option_duration: u64,
-    /// TODO
-    pub allowed_denom: String,
+    /// Bounds in seconds of the duration of new options
+    pub expiry_range: ExpiryRange,
+    /// Grid of expirations new options must be written on
+    pub expiry_series: Option<ExpirySeries>,
+    /// Native denom or cw20 token in which strikes and bids are paid
+    pub allowed_denom: Denom,
     /// TODO
     pub min_bid_increment_bps: u64,
+    /// Amount of time in seconds before the expiration of the option that the settlement auction will begin,
+    /// shorter than the minimum option duration
+    pub settlement_auction_start_offset: u64,
+    /// Pay cw2981 royalties of the underlying collection on settlement
+    pub royalties_enabled: bool,
+    /// Open or sealed bid settlement auctions
+    pub auction_mode: AuctionMode,
+    /// Extend open settlement auctions on late bids
+    pub anti_snipe: Option<AntiSnipe>,
+    /// Bounty paid to whoever settles an option or burns an expired one, zero disables it
+    pub keeper_bounty: Uint128,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    /// Amount of time in seconds before the expiration of the option that the settlement auction will begin.
+    /// Required when migrating from 0.1, which had no settlement auction offset.
+    pub settlement_auction_start_offset: Option<u64>,
+    /// Maximum duration in seconds of new options, required when migrating from 0.1.
+    pub maximum_option_duration: Option<u64>,
 }
 
 #[cw_serde]
@@ -31,6 +143,8 @@ pub enum CallInstrumentExecuteMsg {
         nft_id: TokenId,
         strike: Uint128,
         expiration: Expiration,
+        /// European by default
+        exercise_style: Option<ExerciseStyle>,
     },
 
     /// Mints a new call option for the assets deposited in a particular vault given strike price and expiration.
@@ -39,6 +153,8 @@ pub enum CallInstrumentExecuteMsg {
         asset_id: AssetId,
         strike: Uint128,
         expiration: Expiration,
+        /// European by default
+        exercise_style: Option<ExerciseStyle>,
     },
 
     /// Mints a new call option for the assets deposited in a particular vault given strike price and expiration.
@@ -48,6 +164,27 @@ pub enum CallInstrumentExecuteMsg {
         asset_id: AssetId,
         strike: Uint128,
         expiration: Expiration,
+        /// European by default
+        exercise_style: Option<ExerciseStyle>,
+    },
+
+    /// Mints a call option for each of the given NFTs of the allowed collection. Responds with
+    /// a `MintBatchResponse` as data, the batch fails as a whole if any entry is invalid.
+    MintBatchWithNFT {
+        nft_addr: String,
+        /// The asset id of an entry is the token id of the NFT
+        entries: Vec<MintBatchEntry>,
+        /// European by default
+        exercise_style: Option<ExerciseStyle>,
+    },
+
+    /// Mints a call option for each of the given assets deposited in a particular vault. Responds with
+    /// a `MintBatchResponse` as data, the batch fails as a whole if any entry is invalid.
+    MintBatchWithVault {
+        vault_addr: String,
+        entries: Vec<MintBatchEntry>,
+        /// European by default
+        exercise_style: Option<ExerciseStyle>,
     },
 
     /// Bid in the settlement auction for an option. The paid amount is the bid, and the bidder
@@ -56,6 +193,36 @@ pub enum CallInstrumentExecuteMsg {
         option_id: OptionId,
     },
 
+    /// Commit a sealed bid in the settlement auction of an option. The paid amount is escrowed as a deposit,
+    /// which must cover the bid. The commitment is the sha256 hash built by `utils::bid_commitment`.
+    CommitBid {
+        option_id: OptionId,
+        commitment: Binary,
+    },
+
+    /// Reveal a committed sealed bid after the expiration of the option, within the reveal period.
+    RevealBid {
+        option_id: OptionId,
+        amount: Uint128,
+        salt: String,
+    },
+
+    /// Permissionlessly release the deposit of a sealed bid which didn't win the auction.
+    /// Unrevealed deposits are refunded or forfeited to the writer according to the instrument's rule.
+    ReleaseDeposit {
+        option_id: OptionId,
+        bidder: String,
+    },
+
+    /// Allows the holder of an American style option to exercise it before expiration by paying the strike.
+    /// The holder receives the underlying asset from the vault and the option NFT is burned.
+    Exercise {
+        option_id: OptionId,
+    },
+
+    /// Handles cw20 tokens sent to the instrument when the allowed denom is a cw20 token.
+    Receive(cw20::Cw20ReceiveMsg),
+
     /// Allows the writer to reclaim an entitled asset. This is only
     /// possible when the writer holds the option nft and calls this function.
     ReclaimAsset {
@@ -63,15 +230,50 @@ pub enum CallInstrumentExecuteMsg {
         withdraw: bool,
     },
 
+    /// Allows the writer to change the strike and expiration of an option without bids
+    /// while they still hold the option nft. The vault entitlement is re-imposed with the new expiration.
+    AmendOption {
+        option_id: OptionId,
+        new_strike: Uint128,
+        new_expiration: Expiration,
+    },
+
+    /// Like `AmendOption`, but settles the option and mints a new option id with the new terms
+    /// on the same asset, so the asset stays entitled to the instrument.
+    RollOption {
+        option_id: OptionId,
+        new_strike: Uint128,
+        new_expiration: Expiration,
+    },
+
     /// Permissionlessly settle an expired option when the option expires in the money,
     /// distributing the proceeds to the Writer, Holder, and Bidder.
+    /// The caller receives the keeper bounty out of the spread.
     SettleOption {
         option_id: OptionId,
     },
 
-    /// Allows anyone to burn the instrument NFT for an expired option.
+    /// Settles each of the given options like `SettleOption`, the caller receives the keeper bounty
+    /// of every option. Options already settled, or which can't be settled yet, are skipped.
+    SettleBatch {
+        option_ids: Vec<OptionId>,
+    },
+
+    /// Allows anyone to burn the instrument NFT for an expired option without bids.
+    /// The option is settled, so the asset can be written again, and an entitlement the instrument still holds is released.
+    /// The caller receives the burn bounty the writer funded for the option, if any.
     BurnExpiredOption {
         option_id: OptionId,
+        /// Returns the asset to the writer in the same transaction while the instrument still holds
+        /// the entitlement, only the writer can set it. Once the entitlement lapsed the writer
+        /// withdraws the asset from the vault directly.
+        withdraw: Option<bool>,
+    },
+
+    /// Allows the writer to fund, with native funds attached to the message, a bounty paid to whoever
+    /// burns the option once it expired without bids. An option without bids has no spread to pay it from.
+    FundBurnBounty {
+        option_id: OptionId,
     },
 
     /// Allows the option owner to claim proceeds if the option was settled
@@ -80,11 +282,78 @@ pub enum CallInstrumentExecuteMsg {
         option_id: OptionId,
     },
 
+    /// Allows an outbid bidder to withdraw refunds that could not be sent to them, in every denom.
+    WithdrawRefunds {},
+
+    /// Lists an option for sale by its holder until `expires`, which must not be after the option expiration.
+    /// The option nft stays with the holder, and the listing is cancelled when the option settles.
+    ListOption {
+        option_id: OptionId,
+        premium: Uint128,
+        expires: Expiration,
+        /// Fixed price by default
+        kind: Option<ListingKind>,
+    },
+
+    /// Allows the seller to cancel a listing without bids.
+    CancelListing {
+        option_id: OptionId,
+    },
+
+    /// Buys a listed option with native funds attached to the message. A fixed price listing is paid
+    /// the exact premium, the option nft moves to the buyer and the premium to the seller.
+    /// A premium auction escrows the bid until the listing is finalized.
+    BuyOption {
+        option_id: OptionId,
+    },
+
+    /// Permissionlessly closes an expired premium auction. The best bidder receives the option nft
+    /// and the seller the premium, or the bid is refunded if the seller no longer holds an open option.
+    FinalizeListing {
+        option_id: OptionId,
+    },
+
+    /// Allows the protocol owner to update the instrument settings. Only newly minted options
+    /// are written under the new settings, existing options keep their terms.
+    /// `clear_expiry_series` removes the expiry series, so options can expire at any time again.
+    UpdateConfig {
+        expiry_range: Option<ExpiryRange>,
+        expiry_series: Option<ExpirySeries>,
+        clear_expiry_series: Option<bool>,
+        allowed_denom: Option<Denom>,
+        min_bid_increment_bps: Option<u64>,
+        settlement_auction_start_offset: Option<u64>,
+        royalties_enabled: Option<bool>,
+        keeper_bounty: Option<Uint128>,
+    },
+
     ForTest {}, // TODO delete
 }
 
 impl CustomMsg for CallInstrumentExecuteMsg {}
 
+/// Messages embedded into a cw20 `Send` to the instrument
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Bid in the settlement auction for an option with the sent tokens.
+    Bid { option_id: OptionId },
+
+    /// Exercise an American style option paying the strike with the sent tokens.
+    Exercise { option_id: OptionId },
+
+    /// Commit a sealed bid for an option, escrowing the sent tokens as a deposit.
+    CommitBid {
+        option_id: OptionId,
+        commitment: Binary,
+    },
+
+    /// Buy a listed option, or bid in its premium auction, with the sent tokens.
+    BuyOption { option_id: OptionId },
+
+    /// Fund the burn bounty of an option with the sent tokens.
+    FundBurnBounty { option_id: OptionId },
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum CallInstrumentQueryMsg {
@@ -112,6 +381,171 @@ pub enum CallInstrumentQueryMsg {
 
     #[returns(Expiration)]
     GetExpiration { option_id: OptionId },
+
+    /// Gets the time when the settlement auction of an option begins.
+    #[returns(Expiration)]
+    GetAuctionStart { option_id: OptionId },
+
+    /// Lists options ordered by id, optionally filtered by writer, vault, current high bidder,
+    /// status and expiration range (both bounds inclusive). Filtered by the expiration range only,
+    /// options are ordered by expiration, then by id.
+    #[returns(ListOptionsResponse)]
+    ListOptions {
+        writer: Option<String>,
+        vault: Option<String>,
+        bidder: Option<String>,
+        status: Option<OptionStatus>,
+        expires_after: Option<Expiration>,
+        expires_before: Option<Expiration>,
+        start_after: Option<OptionId>,
+        limit: Option<u32>,
+    },
+
+    /// Lists the upcoming expirations of the expiry series up to the maximum option duration,
+    /// with the number of open options in each. Empty if the instrument has no expiry series.
+    #[returns(ActiveSeriesResponse)]
+    ActiveSeries {
+        start_after: Option<Timestamp>,
+        limit: Option<u32>,
+    },
+
+    /// Lists the open options expiring at the given time, ordered by id.
+    #[returns(ListOptionsResponse)]
+    SeriesOptions {
+        expiration: Timestamp,
+        start_after: Option<OptionId>,
+        limit: Option<u32>,
+    },
+
+    /// Gets the full state of an option together with its owner, pending claim and lifecycle stage.
+    #[returns(OptionDetailsResponse)]
+    OptionDetails { option_id: OptionId },
+
+    /// Batched version of `OptionDetails`, details are returned in the order of the given ids.
+    #[returns(Vec<OptionDetailsResponse>)]
+    OptionsDetails { option_ids: Vec<OptionId> },
+
+    /// Gets the amount of refunds in a denom that can be withdrawn by an outbid bidder,
+    /// the current allowed denom if not set.
+    #[returns(Uint128)]
+    PendingRefunds {
+        address: String,
+        denom: Option<Denom>,
+    },
+
+    /// Gets the settlement terms an option was written under.
+    #[returns(OptionTerms)]
+    OptionTerms { option_id: OptionId },
+
+    /// Gets the sealed bid committed by a bidder in the settlement auction of an option, if any.
+    #[returns(Option<BidCommitment>)]
+    BidCommitment { option_id: OptionId, bidder: String },
+
+    /// Gets how the current high bid would be split if the option was settled now.
+    #[returns(SettlementBreakdown)]
+    SettlementPreview { option_id: OptionId },
+
+    /// Gets the sale listing of an option, if any.
+    #[returns(Option<Listing>)]
+    Listing { option_id: OptionId },
+
+    /// Gets the bounty paid for burning an option once expired without bids, in the denom of the option.
+    #[returns(Uint128)]
+    BurnBounty { option_id: OptionId },
 }
 
 impl CustomMsg for CallInstrumentQueryMsg {}
+
+#[cw_serde]
+pub enum OptionStatus {
+    /// Not settled and not expired
+    Active,
+    /// Expired without being settled
+    Expired,
+    /// Settled or reclaimed by the writer
+    Settled,
+}
+
+#[cw_serde]
+pub enum OptionLifecycle {
+    /// The option is live and the settlement auction has not started yet
+    Active,
+    /// The settlement auction accepts bids
+    AuctionOpen,
+    /// The option expired with a winning bid and can be settled
+    AwaitingSettlement,
+    /// The option expired without bids, the option nft can be burned
+    ExpiredWithoutBids,
+    /// The option was settled or reclaimed by the writer
+    Settled,
+}
+
+#[cw_serde]
+pub struct OptionDetailsResponse {
+    pub option_id: OptionId,
+    pub option: CallInstrument,
+    /// Current owner of the option nft, None when the nft is burned
+    pub owner: Option<Addr>,
+    /// Spread that can be claimed by the option owner after the settlement
+    pub claim: Option<Uint128>,
+    pub lifecycle: OptionLifecycle,
+    /// Minimum amount of the next valid bid in the settlement auction
+    pub min_next_bid: Uint128,
+}
+
+#[cw_serde]
+pub struct SettlementBreakdown {
+    /// The high bid paid for the underlying asset
+    pub bid: Uint128,
+    /// Strike paid to the writer after the strike fee, zero if the writer is the high bidder
+    pub writer_amount: Uint128,
+    /// Spread paid to the option holder after the protocol fee
+    pub holder_amount: Uint128,
+    /// Fee charged on the spread
+    pub protocol_fee: Uint128,
+    /// Fee charged on the strike
+    pub strike_fee: Uint128,
+    /// The address receiving the fees
+    pub fee_recipient: Option<Addr>,
+    /// Royalty of the underlying collection paid out of the spread, at most the whole spread
+    pub royalty_amount: Uint128,
+    /// The address receiving the royalty
+    pub royalty_recipient: Option<Addr>,
+    /// Bounty paid to the caller of the settlement out of the spread
+    pub keeper_bounty: Uint128,
+}
+
+#[cw_serde]
+pub struct OptionResponse {
+    pub option_id: OptionId,
+    pub option: CallInstrument,
+}
+
+#[cw_serde]
+pub struct MintBatchEntry {
+    pub asset_id: AssetId,
+    pub strike: Uint128,
+    pub expiration: Expiration,
+}
+
+#[cw_serde]
+pub struct MintBatchResponse {
+    pub option_ids: Vec<OptionId>,
+}
+
+#[cw_serde]
+pub struct ListOptionsResponse {
+    pub options: Vec<OptionResponse>,
+}
+
+#[cw_serde]
+pub struct SeriesResponse {
+    pub expiration: Timestamp,
+    /// Number of options of the series that are not settled yet
+    pub open_options: u64,
+}
+
+#[cw_serde]
+pub struct ActiveSeriesResponse {
+    pub series: Vec<SeriesResponse>,
+}
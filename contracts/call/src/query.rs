@@ -1,7 +1,33 @@
-use common::types::{AssetId, OptionId};
-use cosmwasm_std::{to_binary, Binary, Deps, StdResult};
+use common::{
+    denom::Denom,
+    errors::ContractError,
+    types::{AssetId, OptionId},
+};
+use cosmwasm_std::{to_binary, Binary, Deps, Env, Order, StdResult, Timestamp};
+use cw721::{Cw721Query, NftInfoResponse};
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
 
-use crate::state::{CallInstrument, VAULT_ASSET_OPTION};
+use crate::{
+    contract::CallInstrumentContract,
+    msg::{
+        ActiveSeriesResponse, ListOptionsResponse, OptionDetailsResponse, OptionMetadata,
+        OptionResponse, OptionStatus, SeriesResponse,
+    },
+    state::{
+        call_instruments, expiration_index_key, CallInstrument, Config, OptionTerms,
+        BID_COMMITMENTS, BURN_BOUNTIES, LISTINGS, OPTION_CLAIMS, PENDING_REFUNDS,
+        VAULT_ASSET_OPTION,
+    },
+    svg::option_token_uri,
+    utils::{
+        auction_start, min_next_bid, option_lifecycle, option_status, protocol_config,
+        settlement_breakdown, settlement_royalty,
+    },
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 pub fn current_bid(deps: Deps, option_id: &OptionId) -> StdResult<Binary> {
     let call = CallInstrument::load(deps.storage, option_id)?;
@@ -45,3 +71,328 @@ pub fn get_expiration(deps: Deps, option_id: &OptionId) -> StdResult<Binary> {
 
     to_binary(&call.expiration)
 }
+
+pub fn get_auction_start(deps: Deps, option_id: &OptionId) -> StdResult<Binary> {
+    let config = Config::load(deps.storage)?.for_option(deps.storage, option_id)?;
+    let call = CallInstrument::load(deps.storage, option_id)?;
+
+    to_binary(&auction_start(
+        &call.expiration,
+        config.settlement_auction_start_offset,
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn list_options(
+    deps: Deps,
+    env: &Env,
+    writer: Option<String>,
+    vault: Option<String>,
+    bidder: Option<String>,
+    status: Option<OptionStatus>,
+    expires_after: Option<Expiration>,
+    expires_before: Option<Expiration>,
+    start_after: Option<OptionId>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let writer = writer.map(|w| deps.api.addr_validate(&w)).transpose()?;
+    let vault = vault.map(|v| deps.api.addr_validate(&v)).transpose()?;
+    let bidder = bidder.map(|b| deps.api.addr_validate(&b)).transpose()?;
+
+    // iterate over the most selective index, the rest of the filters are applied to the loaded options
+    let calls = call_instruments();
+    let iter: Box<dyn Iterator<Item = StdResult<(OptionId, CallInstrument)>> + '_> =
+        if let Some(writer) = &writer {
+            calls.idx.writer.prefix(writer.clone()).range(
+                deps.storage,
+                start,
+                None,
+                Order::Ascending,
+            )
+        } else if let Some(vault) = &vault {
+            calls
+                .idx
+                .vault
+                .prefix(vault.clone())
+                .range(deps.storage, start, None, Order::Ascending)
+        } else if let Some(bidder) = &bidder {
+            calls.idx.bidder.prefix(bidder.to_string()).range(
+                deps.storage,
+                start,
+                None,
+                Order::Ascending,
+            )
+        } else if let Some(status) = &status {
+            calls
+                .idx
+                .settled
+                .prefix((status == &OptionStatus::Settled) as u8)
+                .range(deps.storage, start, None, Order::Ascending)
+        } else if let Some((after, before)) = expiration_index_range(expires_after, expires_before)
+        {
+            // ordered by expiration, the page continues after the expiration of the last option
+            let start_key = match start_after {
+                Some(option_id) => calls
+                    .may_load(deps.storage, option_id)?
+                    .map(|call| (expiration_index_key(&call), option_id)),
+                None => None,
+            };
+            let min = match start_key {
+                Some(key) if key >= (after, 0) => Bound::exclusive(key),
+                _ => Bound::inclusive((after, 0)),
+            };
+            calls.idx.expiration.range(
+                deps.storage,
+                Some(min),
+                Some(Bound::inclusive((before, OptionId::MAX))),
+                Order::Ascending,
+            )
+        } else {
+            calls.range(deps.storage, start, None, Order::Ascending)
+        };
+
+    let is_matching = |call: &CallInstrument| {
+        writer.as_ref().is_none_or(|w| call.writer_addr == *w)
+            && vault.as_ref().is_none_or(|v| call.vault_addr == *v)
+            && bidder
+                .as_ref()
+                .is_none_or(|b| call.bidder.as_ref() == Some(b))
+            && status
+                .as_ref()
+                .is_none_or(|s| option_status(call, &env.block) == *s)
+            && expires_after.is_none_or(|e| call.expiration >= e)
+            && expires_before.is_none_or(|e| call.expiration <= e)
+    };
+
+    let options = iter
+        .filter(|item| item.as_ref().map_or(true, |(_, call)| is_matching(call)))
+        .take(limit)
+        .map(|item| item.map(|(option_id, option)| OptionResponse { option_id, option }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ListOptionsResponse { options })
+}
+
+/// The range of the expiration index covered by the expiration bounds, if at least one is given
+/// and neither is height based.
+fn expiration_index_range(
+    expires_after: Option<Expiration>,
+    expires_before: Option<Expiration>,
+) -> Option<(u64, u64)> {
+    let seconds = |bound: Option<Expiration>, unbounded: u64| match bound {
+        None => Some(unbounded),
+        Some(Expiration::AtTime(time)) => Some(time.seconds()),
+        Some(_) => None,
+    };
+
+    if expires_after.is_none() && expires_before.is_none() {
+        return None;
+    }
+    Some((
+        seconds(expires_after, 0)?,
+        seconds(expires_before, u64::MAX)?,
+    ))
+}
+
+pub fn active_series(
+    deps: Deps,
+    env: &Env,
+    start_after: Option<Timestamp>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let config = Config::load(deps.storage)?;
+    let Some(series) = config.expiry_series else {
+        return to_binary(&ActiveSeriesResponse { series: vec![] });
+    };
+
+    let last = env.block.time.plus_seconds(config.expiry_range.max);
+    let mut expiration = start_after.map_or(env.block.time, |t| t.max(env.block.time));
+    let mut active = vec![];
+    while active.len() < limit {
+        expiration = series.next_after(expiration);
+        if expiration > last {
+            break;
+        }
+        let open_options = call_instruments()
+            .idx
+            .expiration
+            .prefix(expiration.seconds())
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|item| item.as_ref().map_or(true, |(_, call)| !call.settled))
+            .count() as u64;
+        active.push(SeriesResponse {
+            expiration,
+            open_options,
+        });
+    }
+
+    to_binary(&ActiveSeriesResponse { series: active })
+}
+
+pub fn series_options(
+    deps: Deps,
+    env: &Env,
+    expiration: Timestamp,
+    start_after: Option<OptionId>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let options = call_instruments()
+        .idx
+        .expiration
+        .prefix(expiration.seconds())
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref().map_or(true, |(_, call)| {
+                option_status(call, &env.block) == OptionStatus::Active
+            })
+        })
+        .take(limit)
+        .map(|item| item.map(|(option_id, option)| OptionResponse { option_id, option }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ListOptionsResponse { options })
+}
+
+pub fn pending_refunds(deps: Deps, address: String, denom: Option<Denom>) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let denom = match denom {
+        Some(denom) => denom,
+        None => Config::load(deps.storage)?.allowed_denom,
+    };
+    let amount = PENDING_REFUNDS
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(pending, _)| *pending == denom)
+        .map(|(_, amount)| amount)
+        .unwrap_or_default();
+
+    to_binary(&amount)
+}
+
+pub fn option_terms(deps: Deps, option_id: &OptionId) -> StdResult<Binary> {
+    let config = Config::load(deps.storage)?.for_option(deps.storage, option_id)?;
+
+    to_binary(&OptionTerms::from(&config))
+}
+
+pub fn settlement_preview(deps: Deps, option_id: &OptionId) -> Result<Binary, ContractError> {
+    let config = Config::load(deps.storage)?.for_option(deps.storage, option_id)?;
+    let call = CallInstrument::load(deps.storage, option_id)?;
+
+    let breakdown = settlement_breakdown(
+        &call,
+        &protocol_config(&deps.querier, &config)?,
+        settlement_royalty(&deps.querier, &call, &config),
+        config.keeper_bounty,
+    )?;
+
+    Ok(to_binary(&breakdown)?)
+}
+
+pub fn option_details(deps: Deps, env: &Env, option_id: &OptionId) -> StdResult<Binary> {
+    let config = Config::load(deps.storage)?;
+
+    to_binary(&load_option_details(deps, env, &config, option_id)?)
+}
+
+pub fn options_details(
+    deps: Deps,
+    env: &Env,
+    option_ids: Vec<OptionId>,
+) -> Result<Binary, ContractError> {
+    if option_ids.len() > MAX_LIMIT as usize {
+        return Err(ContractError::TooManyOptionIds { max: MAX_LIMIT });
+    }
+    let config = Config::load(deps.storage)?;
+
+    let details = option_ids
+        .iter()
+        .map(|option_id| load_option_details(deps, env, &config, option_id))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(to_binary(&details)?)
+}
+
+fn load_option_details(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    option_id: &OptionId,
+) -> StdResult<OptionDetailsResponse> {
+    let config = &config.for_option(deps.storage, option_id)?;
+    let call = CallInstrument::load(deps.storage, option_id)?;
+    let owner = CallInstrumentContract::default()
+        .tokens
+        .may_load(deps.storage, &option_id.to_string())?
+        .map(|token| token.owner);
+
+    Ok(OptionDetailsResponse {
+        option_id: *option_id,
+        owner,
+        claim: OPTION_CLAIMS.may_load(deps.storage, option_id)?,
+        lifecycle: option_lifecycle(&call, config, &env.block),
+        min_next_bid: min_next_bid(&call, config.min_bid_increment_bps)?,
+        option: call,
+    })
+}
+
+pub fn bid_commitment(deps: Deps, option_id: &OptionId, bidder: String) -> StdResult<Binary> {
+    let bidder = deps.api.addr_validate(&bidder)?;
+
+    to_binary(&BID_COMMITMENTS.may_load(deps.storage, (*option_id, &bidder))?)
+}
+
+pub fn listing(deps: Deps, option_id: &OptionId) -> StdResult<Binary> {
+    to_binary(&LISTINGS.may_load(deps.storage, option_id)?)
+}
+
+pub fn burn_bounty(deps: Deps, option_id: &OptionId) -> StdResult<Binary> {
+    to_binary(
+        &BURN_BOUNTIES
+            .may_load(deps.storage, option_id)?
+            .unwrap_or_default(),
+    )
+}
+
+pub fn nft_info(deps: Deps, token_id: String) -> Result<Binary, ContractError> {
+    let info = CallInstrumentContract::default().nft_info(deps, token_id.clone())?;
+
+    Ok(to_binary(&with_token_uri(&token_id, info)?)?)
+}
+
+pub fn all_nft_info(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: bool,
+) -> Result<Binary, ContractError> {
+    let mut all_info = CallInstrumentContract::default().all_nft_info(
+        deps,
+        env,
+        token_id.clone(),
+        include_expired,
+    )?;
+    all_info.info = with_token_uri(&token_id, all_info.info)?;
+
+    Ok(to_binary(&all_info)?)
+}
+
+fn with_token_uri(
+    token_id: &str,
+    mut info: NftInfoResponse<OptionMetadata>,
+) -> Result<NftInfoResponse<OptionMetadata>, ContractError> {
+    let option_id: OptionId = token_id
+        .parse()
+        .map_err(|_| ContractError::InvalidOptionId(token_id.to_string()))?;
+    info.token_uri = Some(option_token_uri(&option_id, &info.extension));
+
+    Ok(info)
+}
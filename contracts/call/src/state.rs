@@ -1,17 +1,19 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Api, StdError, StdResult, Storage, Uint128, Uint64};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    ensure, Addr, Api, Binary, BlockInfo, StdError, StdResult, Storage, Uint128, Uint64,
+};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use cw_utils::Expiration;
 
 use common::{
     addr::{assert_valid_addr, PREFIX},
     denom::Denom,
     errors::ContractError,
-    types::{AssetId, OptionId},
+    types::{AssetId, ExerciseStyle, ExpiryRange, ExpirySeries, OptionId},
 };
 use macros::ConfigStorage;
 
-use crate::msg::InstantiateMsg;
+use crate::msg::{AntiSnipe, AuctionMode, InstantiateMsg, ListingKind};
 
 /// Covered call option settings store
 const CONFIG: Item<Config> = Item::new("config");
@@ -25,14 +27,27 @@ pub struct Config {
     pub allowed_underlying_nft: Addr,
     /// The address of the vault factory.
     pub vault_factory_addr: Addr,
-    /// Default min duration in seconds of an created option
-    pub minimum_option_duration: u64,
-    /// TODO
+    /// Bounds in seconds of the duration of new options
+    pub expiry_range: ExpiryRange,
+    /// Grid of expirations new options must be written on, any expiration within the range if not set
+    pub expiry_series: Option<ExpirySeries>,
+    /// Native denom or cw20 token in which strikes and bids are paid
     pub allowed_denom: Denom,
     /// Min amount of the current bid that the new bid
     /// must exceed the current bid by in order to be considered valid.
     /// This amount is expressed in basis points (i.e. 1/100th of 1%)
     pub min_bid_increment_bps: u64,
+    /// Amount of time in seconds before the expiration of the option
+    /// that the settlement auction will begin.
+    pub settlement_auction_start_offset: u64,
+    /// Flag that enables paying cw2981 royalties of the underlying collection on settlement.
+    pub royalties_enabled: bool,
+    /// Open or sealed bid settlement auctions
+    pub auction_mode: AuctionMode,
+    /// Extension of open settlement auctions on late bids, disabled if not set
+    pub anti_snipe: Option<AntiSnipe>,
+    /// Amount paid to whoever settles an option, out of the spread.
+    pub keeper_bounty: Uint128,
 }
 
 impl Config {
@@ -42,19 +57,70 @@ impl Config {
         api.addr_validate(self.protocol_addr.as_str())?;
         api.addr_validate(self.allowed_underlying_nft.as_str())?;
         api.addr_validate(self.vault_factory_addr.as_str())?;
+        self.allowed_denom.validate(api)?;
+        self.expiry_range.validate()?;
+        if let Some(series) = &self.expiry_series {
+            series.validate()?;
+        }
+        // the settlement auction of an option can't open before the option is written
+        ensure!(
+            self.settlement_auction_start_offset < self.expiry_range.min,
+            ContractError::AuctionStartOffsetTooLong {
+                min: self.expiry_range.min
+            }
+        );
+        Ok(())
+    }
+
+    /// Checks the expiration of an option written at the given block against the range and the series.
+    pub fn check_expiration(
+        &self,
+        expiration: &Expiration,
+        block: &BlockInfo,
+    ) -> Result<(), ContractError> {
+        self.expiry_range.check(expiration, block)?;
+        if let Some(series) = &self.expiry_series {
+            series.check(expiration)?;
+        }
         Ok(())
     }
 }
 
+impl Config {
+    /// Returns the config with the terms an option was written under, which config updates don't change.
+    /// Options without recorded terms follow the current config.
+    pub fn for_option(&self, store: &dyn Storage, option_id: &OptionId) -> StdResult<Config> {
+        let Some(terms) = OPTION_TERMS.may_load(store, option_id)? else {
+            return Ok(self.clone());
+        };
+        Ok(Config {
+            allowed_denom: terms.allowed_denom,
+            min_bid_increment_bps: terms.min_bid_increment_bps,
+            settlement_auction_start_offset: terms.settlement_auction_start_offset,
+            royalties_enabled: terms.royalties_enabled,
+            auction_mode: terms.auction_mode,
+            anti_snipe: terms.anti_snipe,
+            keeper_bounty: terms.keeper_bounty,
+            ..self.clone()
+        })
+    }
+}
+
 impl From<InstantiateMsg> for Config {
     fn from(value: InstantiateMsg) -> Self {
         Self {
             protocol_addr: Addr::unchecked(value.protocol_addr),
             allowed_underlying_nft: Addr::unchecked(value.allowed_underlying_nft),
             vault_factory_addr: Addr::unchecked(value.vault_factory_addr),
-            minimum_option_duration: value.minimum_option_duration,
-            allowed_denom: value.allowed_denom.into(),
+            expiry_range: value.expiry_range,
+            expiry_series: value.expiry_series,
+            allowed_denom: value.allowed_denom,
             min_bid_increment_bps: value.min_bid_increment_bps,
+            settlement_auction_start_offset: value.settlement_auction_start_offset,
+            royalties_enabled: value.royalties_enabled,
+            auction_mode: value.auction_mode,
+            anti_snipe: value.anti_snipe,
+            keeper_bounty: value.keeper_bounty,
         }
     }
 }
@@ -67,8 +133,76 @@ impl From<InstantiateMsg> for Config {
 /// Counter for options
 pub const CALL_INSTRUMENTS_COUNT: Item<u64> = Item::new("call_options_count");
 
+/// Indexes of the call options storage, used by the listing queries.
+pub struct CallInstrumentIndexes<'a> {
+    pub writer: MultiIndex<'a, Addr, CallInstrument, OptionId>,
+    pub vault: MultiIndex<'a, Addr, CallInstrument, OptionId>,
+    /// Indexed by the current high bidder, empty string when there are no bids
+    pub bidder: MultiIndex<'a, String, CallInstrument, OptionId>,
+    /// Indexed by the settled flag, 1 if settled
+    pub settled: MultiIndex<'a, u8, CallInstrument, OptionId>,
+    /// Indexed by the expiration time in seconds, 0 for legacy height based expirations
+    pub expiration: MultiIndex<'a, u64, CallInstrument, OptionId>,
+}
+
+impl<'a> IndexList<CallInstrument> for CallInstrumentIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<CallInstrument>> + '_> {
+        let v: Vec<&dyn Index<CallInstrument>> = vec![
+            &self.writer,
+            &self.vault,
+            &self.bidder,
+            &self.settled,
+            &self.expiration,
+        ];
+        Box::new(v.into_iter())
+    }
+}
+
 /// Storage of all existing options contracts.
-pub const CALL_INSTRUMENTS: Map<&OptionId, CallInstrument> = Map::new("call_options");
+pub fn call_instruments<'a>() -> IndexedMap<'a, OptionId, CallInstrument, CallInstrumentIndexes<'a>>
+{
+    let indexes = CallInstrumentIndexes {
+        writer: MultiIndex::new(
+            |_pk, call| call.writer_addr.clone(),
+            "call_options",
+            "call_options__writer",
+        ),
+        vault: MultiIndex::new(
+            |_pk, call| call.vault_addr.clone(),
+            "call_options",
+            "call_options__vault",
+        ),
+        bidder: MultiIndex::new(
+            |_pk, call| {
+                call.bidder
+                    .as_ref()
+                    .map(|bidder| bidder.to_string())
+                    .unwrap_or_default()
+            },
+            "call_options",
+            "call_options__bidder",
+        ),
+        settled: MultiIndex::new(
+            |_pk, call| call.settled as u8,
+            "call_options",
+            "call_options__settled",
+        ),
+        expiration: MultiIndex::new(
+            |_pk, call| expiration_index_key(call),
+            "call_options",
+            "call_options__expiration",
+        ),
+    };
+    IndexedMap::new("call_options", indexes)
+}
+
+/// The key of an option in the expiration index, 0 for legacy height based expirations.
+pub(crate) fn expiration_index_key(call: &CallInstrument) -> u64 {
+    match call.expiration {
+        Expiration::AtTime(time) => time.seconds(),
+        _ => 0,
+    }
+}
 
 /// The metadata for each covered call option stored within the protocol
 #[cw_serde]
@@ -91,6 +225,10 @@ pub struct CallInstrument {
     // TODO Once this flag is set, ETH should not?
     /// Flag that marks when a settlement action has taken place successfully.
     pub settled: bool,
+    /// Whether the holder can exercise the option before expiration
+    pub exercise_style: ExerciseStyle,
+    /// The end of the settlement auction, which is the expiration unless late bids extended the auction
+    pub auction_end: Expiration,
 }
 
 impl CallInstrument {
@@ -103,15 +241,15 @@ impl CallInstrument {
     }
 
     pub fn load(store: &dyn Storage, option_id: &OptionId) -> StdResult<CallInstrument> {
-        CALL_INSTRUMENTS.load(store, option_id)
+        call_instruments().load(store, *option_id)
     }
 
     pub fn save(&self, store: &mut dyn Storage, option_id: &OptionId) -> StdResult<()> {
-        CALL_INSTRUMENTS.save(store, option_id, self)
+        call_instruments().save(store, *option_id, self)
     }
 
     pub fn update(&self, store: &mut dyn Storage, option_id: &OptionId) -> StdResult<()> {
-        CALL_INSTRUMENTS.update(store, option_id, |option| match option {
+        call_instruments().update(store, *option_id, |option| match option {
             Some(_) => Ok(self.to_owned()),
             None => Err(StdError::generic_err("updated option not found")),
         })?;
@@ -139,11 +277,93 @@ impl CallInstrument {
     }
 }
 
+/// The settlement terms of the instrument at the time an option was written.
+#[cw_serde]
+pub struct OptionTerms {
+    pub allowed_denom: Denom,
+    pub min_bid_increment_bps: u64,
+    pub settlement_auction_start_offset: u64,
+    pub royalties_enabled: bool,
+    pub auction_mode: AuctionMode,
+    pub anti_snipe: Option<AntiSnipe>,
+    pub keeper_bounty: Uint128,
+}
+
+impl From<&Config> for OptionTerms {
+    fn from(config: &Config) -> Self {
+        Self {
+            allowed_denom: config.allowed_denom.clone(),
+            min_bid_increment_bps: config.min_bid_increment_bps,
+            settlement_auction_start_offset: config.settlement_auction_start_offset,
+            royalties_enabled: config.royalties_enabled,
+            auction_mode: config.auction_mode.clone(),
+            anti_snipe: config.anti_snipe.clone(),
+            keeper_bounty: config.keeper_bounty,
+        }
+    }
+}
+
+/// Terms of each option, saved when the option is minted.
+pub const OPTION_TERMS: Map<&OptionId, OptionTerms> = Map::new("option_terms");
+
 /// Mapping to store the amount of eth in wei that may be claimed by the current owner the option nft.
 pub const OPTION_CLAIMS: Map<&OptionId, Uint128> = Map::new("option_claims");
 
-/// Flag that can be set to pause this call option contract.
-pub const PAUSED: Item<bool> = Item::new("paused");
+/// Bounties funded by writers for burning their options once expired without bids, in the denom of the option.
+/// A bounty is returned to the pending refunds of the writer when the option ends any other way.
+pub const BURN_BOUNTIES: Map<&OptionId, Uint128> = Map::new("burn_bounties");
+
+/// Refunds of outbid bidders that could not be sent, withdrawn by the bidders themselves.
+/// Refunds are kept by denom, as options written under different terms can be bid in different denoms.
+pub const PENDING_REFUNDS: Map<&Addr, Vec<(Denom, Uint128)>> = Map::new("pending_refunds");
+
+/// The refunds sent in sub-messages, by the reply id of their sub-message. Each refund is removed
+/// when its sub-message replies, and credited to `PENDING_REFUNDS` if the send failed.
+pub const REFUNDS_IN_FLIGHT: Map<u64, (Addr, Denom, Uint128)> =
+    Map::new("refunds_in_flight_by_reply_id");
+
+/// A sealed bid committed in the settlement auction of an option.
+#[cw_serde]
+pub struct BidCommitment {
+    /// The sha256 hash of the bid, see `utils::bid_commitment`
+    pub commitment: Binary,
+    /// The escrowed amount, which must cover the revealed bid
+    pub deposit: Uint128,
+    /// The revealed bid, which includes the strike if the bidder is the writer
+    pub revealed_bid: Option<Uint128>,
+}
+
+/// Sealed bids by option id and bidder.
+pub const BID_COMMITMENTS: Map<(OptionId, &Addr), BidCommitment> = Map::new("bid_commitments");
+
+/// State of the sealed bid auction of an option.
+#[cw_serde]
+#[derive(Default)]
+pub struct SealedAuction {
+    /// The second highest revealed bid, paid by the winner of second price auctions
+    pub second_bid: Uint128,
+    /// Set when the option was settled before expiration, so the bids could never be revealed
+    pub cancelled: bool,
+}
+
+pub const SEALED_AUCTIONS: Map<&OptionId, SealedAuction> = Map::new("sealed_auctions");
+
+/// A sale of an option nft by its holder. The nft is not escrowed, so the listing
+/// can only be bought while the seller still holds the option.
+#[cw_serde]
+pub struct Listing {
+    pub seller: Addr,
+    /// The fixed price, or the reserve of a premium auction
+    pub premium: Uint128,
+    pub expires: Expiration,
+    pub kind: ListingKind,
+    /// The best bid of a premium auction, escrowed by the instrument
+    pub bid: Uint128,
+    pub bidder: Option<Addr>,
+}
+
+/// Sale listings by option id.
+pub const LISTINGS: Map<&OptionId, Listing> = Map::new("listings");
 
 /// Storage of current call active call option for a specific asset.
 /// The call option is is referenced via the option_id.
@@ -157,5 +377,3 @@ pub fn update_vault_asset_option(
 ) -> StdResult<OptionId> {
     VAULT_ASSET_OPTION.update(store, (vault_addr, asset_id), |_| Ok(option_id))
 }
-
-// TODO use IndexedMap
@@ -0,0 +1,82 @@
+use common::types::OptionId;
+use cosmwasm_std::Binary;
+use cw_utils::Expiration;
+
+use crate::msg::OptionMetadata;
+
+const WIDTH: u32 = 350;
+const HEIGHT: u32 = 350;
+
+/// Renders the image of an option nft as a base64 encoded svg data uri.
+pub fn option_token_uri(option_id: &OptionId, metadata: &OptionMetadata) -> String {
+    format!(
+        "data:image/svg+xml;base64,{}",
+        Binary::from(option_svg(option_id, metadata).as_bytes()).to_base64()
+    )
+}
+
+/// Renders the image of an option nft with its terms and the current high bid.
+pub fn option_svg(option_id: &OptionId, metadata: &OptionMetadata) -> String {
+    let status = if metadata.settled {
+        "Settled"
+    } else {
+        "Active"
+    };
+    let lines = [
+        format!("Call Option #{}", option_id),
+        format!("Collection: {}", metadata.underlying_nft),
+        format!("Token: #{}", metadata.asset_id),
+        format!("Strike: {} {}", metadata.strike, metadata.denom),
+        format!("Expiration: {}", format_expiration(&metadata.expiration)),
+        format!("High bid: {} {}", metadata.bid, metadata.denom),
+        format!("Status: {}", status),
+    ];
+
+    let text = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            format!(
+                r#"<text x="20" y="{}" class="{}">{}</text>"#,
+                40 + i * 40,
+                if i == 0 { "title" } else { "line" },
+                escape_xml(line)
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            "<style>.title{{fill:#fff;font:bold 20px monospace}}.line{{fill:#fff;font:12px monospace}}</style>",
+            r##"<rect width="100%" height="100%" fill="#1b1b3a"/>"##,
+            "{text}</svg>"
+        ),
+        w = WIDTH,
+        h = HEIGHT,
+        text = text
+    )
+}
+
+fn format_expiration(expiration: &Expiration) -> String {
+    match expiration {
+        Expiration::AtTime(time) => format!("{} (unix)", time.seconds()),
+        Expiration::AtHeight(height) => format!("block {}", height),
+        Expiration::Never {} => "never".to_string(),
+    }
+}
+
+/// Escapes user provided values, like collection addresses or asset ids, before embedding them in the svg.
+fn escape_xml(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&apos;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
@@ -1,10 +1,21 @@
-use common::{denom::Denom, errors::ContractError};
+use common::{
+    denom::Denom,
+    errors::ContractError,
+    events::{BidPlaced, BidRefunded, ExpiredOptionBurned},
+    types::{ExerciseStyle, ExpiryRange, ExpirySeries, OptionId},
+};
 use cosmwasm_std::{
-    from_binary,
-    testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
-    Coin, Empty, MessageInfo, OwnedDeps, StdError, Uint128,
+    coins, from_binary,
+    testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+        MOCK_CONTRACT_ADDR,
+    },
+    to_binary, Addr, BankMsg, Binary, Coin, ContractResult, Env, Event, MessageInfo, OwnedDeps,
+    Reply, Response, StdError, SubMsg, SubMsgResponse, SubMsgResult, SystemResult, Timestamp,
+    Uint128, WasmQuery,
 };
 use cw2::ContractVersion;
+use cw_utils::Expiration;
 
 use crate::{
     contract::{self, *},
@@ -13,8 +24,30 @@ use crate::{
 };
 
 const USER: &str = "user_addr";
+const WRITER: &str = "writer_addr";
 const NFT_ADDR: &str = "bayc_nft_addr";
 const NFT_ID: &str = "id001";
+const HOUR: u64 = 3600;
+const DAY: u64 = 24 * HOUR;
+
+/// Mocks the protocol contract, which answers whether the protocol is paused.
+fn mock_protocol(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, is_paused: bool) {
+    deps.querier
+        .update_wasm(move |_| SystemResult::Ok(ContractResult::Ok(to_binary(&is_paused).unwrap())));
+}
+
+/// Builds the nft metadata of an option already saved in storage.
+fn option_metadata(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    option_id: u64,
+) -> OptionMetadata {
+    let call = CallInstrument::load(deps.as_ref().storage, &option_id).unwrap();
+    let config = Config::load(deps.as_ref().storage)
+        .unwrap()
+        .for_option(deps.as_ref().storage, &option_id)
+        .unwrap();
+    OptionMetadata::new(&call, &config)
+}
 
 #[allow(dead_code)]
 #[allow(let_underscore_lock)]
@@ -23,6 +56,7 @@ fn setup(
     funds: &[Coin],
 ) -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, MessageInfo) {
     let mut deps = mock_dependencies();
+    mock_protocol(&mut deps, false);
     let info = mock_info(info_sender, funds);
 
     let msg = InstantiateMsg {
@@ -31,9 +65,18 @@ fn setup(
         protocol_addr: "protocol_addr".to_string(),
         allowed_underlying_nft: NFT_ADDR.to_string(),
         vault_factory_addr: "vault_factory_addr".to_string(),
-        minimum_option_duration: 1,
-        allowed_denom: "ATOM".to_string(),
+        expiry_range: ExpiryRange {
+            min: 2 * HOUR,
+            max: 365 * DAY,
+        },
+        expiry_series: None,
+        allowed_denom: Denom::from("ATOM"),
         min_bid_increment_bps: 100,
+        settlement_auction_start_offset: HOUR,
+        royalties_enabled: false,
+        auction_mode: AuctionMode::Open,
+        anti_snipe: None,
+        keeper_bounty: Uint128::zero(),
     };
     let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg);
 
@@ -50,9 +93,18 @@ fn proper_initialization() {
         protocol_addr: "protocol_addr".to_string(),
         allowed_underlying_nft: NFT_ADDR.to_string(),
         vault_factory_addr: "vault_factory_addr".to_string(),
-        minimum_option_duration: 1,
-        allowed_denom: "ATOM".to_string(),
+        expiry_range: ExpiryRange {
+            min: 2 * HOUR,
+            max: 365 * DAY,
+        },
+        expiry_series: None,
+        allowed_denom: Denom::from("ATOM"),
         min_bid_increment_bps: 100,
+        settlement_auction_start_offset: HOUR,
+        royalties_enabled: false,
+        auction_mode: AuctionMode::Open,
+        anti_snipe: None,
+        keeper_bounty: Uint128::zero(),
     };
 
     let mut deps = mock_dependencies();
@@ -66,9 +118,16 @@ fn proper_initialization() {
     assert_eq!(config.protocol_addr, "protocol_addr");
     assert_eq!(config.vault_factory_addr, "vault_factory_addr");
     assert_eq!(config.allowed_underlying_nft, "bayc_nft_addr".to_string());
-    assert_eq!(config.minimum_option_duration, 1);
+    assert_eq!(
+        config.expiry_range,
+        ExpiryRange {
+            min: 2 * HOUR,
+            max: 365 * DAY
+        }
+    );
     assert_eq!(config.allowed_denom, Denom::from("ATOM"));
     assert_eq!(config.min_bid_increment_bps, 100);
+    assert_eq!(config.settlement_auction_start_offset, HOUR);
 
     let version = cw2::get_contract_version(&deps.storage).unwrap();
     assert_eq!(
@@ -98,17 +157,13 @@ fn cant_mint_with_wrong_nft() {
             nft_id: wrong_nft.to_string(),
             strike: Uint128::zero(),
             expiration: cw_utils::Expiration::Never {},
+            exercise_style: None,
         },
     };
 
     let res = contract::execute(deps.as_mut(), mock_env(), info, msg);
 
-    assert_eq!(
-        res,
-        Err(ContractError::Std(StdError::GenericErr {
-            msg: "mint_with_nft - nft not allowed".to_string()
-        }))
-    );
+    assert_eq!(res, Err(ContractError::NftNotAllowed {}));
 }
 
 #[test]
@@ -119,7 +174,17 @@ fn cant_direct_minting() {
         token_id: NFT_ID.to_string(),
         owner: USER.to_string(),
         token_uri: None,
-        extension: Empty {},
+        extension: OptionMetadata {
+            underlying_nft: Addr::unchecked(NFT_ADDR),
+            asset_id: NFT_ID.to_string(),
+            vault_addr: Addr::unchecked("vault_addr"),
+            strike: Uint128::new(500_000),
+            denom: Denom::from("ATOM"),
+            expiration: Expiration::Never {},
+            exercise_style: ExerciseStyle::European,
+            bid: Uint128::zero(),
+            settled: false,
+        },
     };
     let res = contract::execute(deps.as_mut(), mock_env(), info, msg);
 
@@ -131,3 +196,2316 @@ fn cant_direct_minting() {
         }))
     )
 }
+
+#[test]
+fn cant_bid_before_auction_start() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    let expiration = Expiration::AtTime(mock_env().block.time.plus_seconds(2 * DAY));
+    CallInstrument {
+        asset_id: NFT_ID.to_string(),
+        writer_addr: Addr::unchecked(WRITER),
+        expiration,
+        vault_addr: Addr::unchecked("vault_addr"),
+        strike: Uint128::new(5),
+        bid: Uint128::zero(),
+        bidder: None,
+        settled: false,
+        exercise_style: ExerciseStyle::European,
+        auction_end: expiration,
+    }
+    .save(deps.as_mut().storage, &1)
+    .unwrap();
+
+    let auction_start: Expiration = from_binary(
+        &contract::query(
+            deps.as_ref(),
+            mock_env(),
+            cw721_base::QueryMsg::Extension {
+                msg: crate::QueryMsg::GetAuctionStart { option_id: 1 },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        auction_start,
+        Expiration::AtTime(mock_env().block.time.plus_seconds(2 * DAY - HOUR))
+    );
+
+    let msg = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::Bid { option_id: 1 },
+    };
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &coins(10, "ATOM")),
+        msg,
+    );
+
+    assert_eq!(res, Err(ContractError::AuctionNotStarted {}));
+}
+
+#[test]
+fn auction_start_offset_must_be_shorter_than_minimum_duration() {
+    let msg = InstantiateMsg {
+        name: "call-option-nft".to_string(),
+        symbol: "CALOPT".to_string(),
+        protocol_addr: "protocol_addr".to_string(),
+        allowed_underlying_nft: NFT_ADDR.to_string(),
+        vault_factory_addr: "vault_factory_addr".to_string(),
+        expiry_range: ExpiryRange {
+            min: 2 * HOUR,
+            max: 365 * DAY,
+        },
+        expiry_series: None,
+        allowed_denom: Denom::from("ATOM"),
+        min_bid_increment_bps: 100,
+        settlement_auction_start_offset: 2 * HOUR,
+        royalties_enabled: false,
+        auction_mode: AuctionMode::Open,
+        anti_snipe: None,
+        keeper_bounty: Uint128::zero(),
+    };
+    let mut deps = mock_dependencies();
+    let res = instantiate(deps.as_mut(), mock_env(), mock_info(USER, &[]), msg);
+    assert_eq!(
+        res,
+        Err(ContractError::AuctionStartOffsetTooLong { min: 2 * HOUR })
+    );
+
+    // the auction of an option written under earlier terms opens at the earliest at time zero
+    assert_eq!(
+        crate::utils::auction_start(&Expiration::AtTime(Timestamp::from_seconds(HOUR)), DAY),
+        Expiration::AtTime(Timestamp::from_seconds(0))
+    );
+}
+
+#[test]
+fn cant_bid_with_not_allowed_cw20() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    let msg = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::Receive(cw20::Cw20ReceiveMsg {
+            sender: USER.to_string(),
+            amount: Uint128::new(10),
+            msg: to_binary(&ReceiveMsg::Bid { option_id: 1 }).unwrap(),
+        }),
+    };
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("cw20_token_addr", &[]),
+        msg,
+    );
+
+    assert_eq!(res, Err(ContractError::DenomNotAllowed {}));
+}
+
+#[test]
+fn list_options_by_filters() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    let now = mock_env().block.time;
+    let options = [
+        (WRITER, "vault_1", None, false, 2 * DAY),
+        (WRITER, "vault_2", Some(USER), false, 3 * DAY),
+        (USER, "vault_1", None, true, 4 * DAY),
+        (USER, "vault_2", None, false, 2 * DAY + HOUR),
+    ];
+    for (i, (writer, vault, bidder, settled, expires_in)) in options.into_iter().enumerate() {
+        CallInstrument {
+            asset_id: NFT_ID.to_string(),
+            writer_addr: Addr::unchecked(writer),
+            expiration: Expiration::AtTime(now.plus_seconds(expires_in)),
+            vault_addr: Addr::unchecked(vault),
+            strike: Uint128::new(5),
+            bid: Uint128::zero(),
+            bidder: bidder.map(Addr::unchecked),
+            settled,
+            exercise_style: ExerciseStyle::European,
+            auction_end: Expiration::AtTime(now.plus_seconds(expires_in)),
+        }
+        .save(deps.as_mut().storage, &(i as u64 + 1))
+        .unwrap();
+    }
+
+    let list = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, msg| -> Vec<u64> {
+        from_binary::<ListOptionsResponse>(
+            &contract::query(
+                deps.as_ref(),
+                mock_env(),
+                cw721_base::QueryMsg::Extension { msg },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .options
+        .into_iter()
+        .map(|o| o.option_id)
+        .collect()
+    };
+    let all = CallInstrumentQueryMsg::ListOptions {
+        writer: None,
+        vault: None,
+        bidder: None,
+        status: None,
+        expires_after: None,
+        expires_before: None,
+        start_after: None,
+        limit: None,
+    };
+
+    assert_eq!(list(&deps, all), vec![1, 2, 3, 4]);
+
+    let by_writer = CallInstrumentQueryMsg::ListOptions {
+        writer: Some(WRITER.to_string()),
+        vault: Some("vault_1".to_string()),
+        bidder: None,
+        status: None,
+        expires_after: None,
+        expires_before: None,
+        start_after: None,
+        limit: None,
+    };
+    assert_eq!(list(&deps, by_writer), vec![1]);
+
+    let by_bidder = CallInstrumentQueryMsg::ListOptions {
+        writer: None,
+        vault: None,
+        bidder: Some(USER.to_string()),
+        status: None,
+        expires_after: None,
+        expires_before: None,
+        start_after: None,
+        limit: None,
+    };
+    assert_eq!(list(&deps, by_bidder), vec![2]);
+
+    let active = CallInstrumentQueryMsg::ListOptions {
+        writer: None,
+        vault: None,
+        bidder: None,
+        status: Some(OptionStatus::Active),
+        expires_after: Some(Expiration::AtTime(now.plus_seconds(3 * DAY))),
+        expires_before: None,
+        start_after: None,
+        limit: None,
+    };
+    assert_eq!(list(&deps, active), vec![2]);
+
+    let page = CallInstrumentQueryMsg::ListOptions {
+        writer: None,
+        vault: None,
+        bidder: None,
+        status: None,
+        expires_after: None,
+        expires_before: None,
+        start_after: Some(1),
+        limit: Some(1),
+    };
+    assert_eq!(list(&deps, page), vec![2]);
+
+    // filtered by the expiration range only, options are listed in the order they expire
+    let expiring = |start_after| CallInstrumentQueryMsg::ListOptions {
+        writer: None,
+        vault: None,
+        bidder: None,
+        status: None,
+        expires_after: Some(Expiration::AtTime(now.plus_seconds(2 * DAY))),
+        expires_before: Some(Expiration::AtTime(now.plus_seconds(3 * DAY))),
+        start_after,
+        limit: Some(2),
+    };
+    assert_eq!(list(&deps, expiring(None)), vec![1, 4]);
+    assert_eq!(list(&deps, expiring(Some(4))), vec![2]);
+}
+
+#[test]
+fn option_details() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    let now = mock_env().block.time;
+    for (option_id, expires_in, bid) in [(1, 2 * DAY, 0), (2, HOUR / 2, 1_000_000)] {
+        CallInstrument {
+            asset_id: NFT_ID.to_string(),
+            writer_addr: Addr::unchecked(WRITER),
+            expiration: Expiration::AtTime(now.plus_seconds(expires_in)),
+            vault_addr: Addr::unchecked("vault_addr"),
+            strike: Uint128::new(500_000),
+            bid: Uint128::new(bid),
+            bidder: (bid > 0).then(|| Addr::unchecked(USER)),
+            settled: false,
+            exercise_style: ExerciseStyle::European,
+            auction_end: Expiration::AtTime(now.plus_seconds(expires_in)),
+        }
+        .save(deps.as_mut().storage, &option_id)
+        .unwrap();
+    }
+    let extension = option_metadata(&deps, 1);
+    CallInstrumentContract::default()
+        .tokens
+        .save(
+            deps.as_mut().storage,
+            "1",
+            &cw721_base::state::TokenInfo {
+                owner: Addr::unchecked(WRITER),
+                approvals: vec![],
+                token_uri: None,
+                extension,
+            },
+        )
+        .unwrap();
+
+    let details: OptionDetailsResponse = from_binary(
+        &contract::query(
+            deps.as_ref(),
+            mock_env(),
+            cw721_base::QueryMsg::Extension {
+                msg: CallInstrumentQueryMsg::OptionDetails { option_id: 1 },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(details.owner, Some(Addr::unchecked(WRITER)));
+    assert_eq!(details.claim, None);
+    assert_eq!(details.lifecycle, OptionLifecycle::Active);
+    assert_eq!(details.min_next_bid, Uint128::new(500_000));
+
+    let details: Vec<OptionDetailsResponse> = from_binary(
+        &contract::query(
+            deps.as_ref(),
+            mock_env(),
+            cw721_base::QueryMsg::Extension {
+                msg: CallInstrumentQueryMsg::OptionsDetails {
+                    option_ids: vec![2, 1],
+                },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        details.iter().map(|d| d.option_id).collect::<Vec<_>>(),
+        vec![2, 1]
+    );
+    assert_eq!(details[0].owner, None);
+    assert_eq!(details[0].lifecycle, OptionLifecycle::AuctionOpen);
+    // 1% min bid increment
+    assert_eq!(details[0].min_next_bid, Uint128::new(1_010_000));
+
+    let res = contract::query(
+        deps.as_ref(),
+        mock_env(),
+        cw721_base::QueryMsg::Extension {
+            msg: CallInstrumentQueryMsg::OptionsDetails {
+                option_ids: (1..=31).collect(),
+            },
+        },
+    );
+    assert_eq!(res, Err(ContractError::TooManyOptionIds { max: 30 }));
+}
+
+#[test]
+fn settlement_breakdown_charges_fees() {
+    let mut call = CallInstrument {
+        asset_id: NFT_ID.to_string(),
+        writer_addr: Addr::unchecked(WRITER),
+        expiration: Expiration::AtTime(mock_env().block.time),
+        vault_addr: Addr::unchecked("vault_addr"),
+        strike: Uint128::new(1_000_000),
+        bid: Uint128::new(1_500_000),
+        bidder: Some(Addr::unchecked(USER)),
+        settled: false,
+        exercise_style: ExerciseStyle::European,
+        auction_end: Expiration::AtTime(mock_env().block.time),
+    };
+    let protocol_config = protocol::state::Config {
+        protocol_fee_bps: 1_000,
+        strike_fee_bps: 100,
+        fee_recipient: Some(Addr::unchecked("fee_recipient")),
+        ..Default::default()
+    };
+
+    // the keeper bounty is paid out of the spread
+    let breakdown =
+        crate::utils::settlement_breakdown(&call, &protocol_config, None, Uint128::new(5_000))
+            .unwrap();
+    assert_eq!(
+        breakdown,
+        SettlementBreakdown {
+            bid: Uint128::new(1_500_000),
+            writer_amount: Uint128::new(990_000),
+            holder_amount: Uint128::new(445_000),
+            protocol_fee: Uint128::new(50_000),
+            strike_fee: Uint128::new(10_000),
+            fee_recipient: Some(Addr::unchecked("fee_recipient")),
+            royalty_amount: Uint128::zero(),
+            royalty_recipient: None,
+            keeper_bounty: Uint128::new(5_000),
+        }
+    );
+
+    // the writer bidding on the spread doesn't receive the strike, so no strike fee is charged
+    call.bidder = Some(Addr::unchecked(WRITER));
+    let breakdown =
+        crate::utils::settlement_breakdown(&call, &protocol_config, None, Uint128::zero()).unwrap();
+    assert_eq!(breakdown.writer_amount, Uint128::zero());
+    assert_eq!(breakdown.strike_fee, Uint128::zero());
+    assert_eq!(breakdown.holder_amount, Uint128::new(450_000));
+
+    // no fees are charged without a recipient
+    let breakdown = crate::utils::settlement_breakdown(
+        &call,
+        &protocol::state::Config::default(),
+        None,
+        Uint128::zero(),
+    )
+    .unwrap();
+    assert_eq!(breakdown.protocol_fee, Uint128::zero());
+    assert_eq!(breakdown.holder_amount, Uint128::new(500_000));
+
+    // an option without bids can't be settled
+    call.bidder = None;
+    let res = crate::utils::settlement_breakdown(&call, &protocol_config, None, Uint128::zero());
+    assert_eq!(res, Err(ContractError::NoWinningBidder {}));
+}
+
+#[test]
+fn settlement_preview_without_bids_fails() {
+    let (mut deps, _) = setup(USER, &[]);
+    deps.querier.update_wasm(|_| {
+        let response = to_binary(&protocol::state::Config::default());
+        SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+    });
+    CallInstrument {
+        asset_id: NFT_ID.to_string(),
+        writer_addr: Addr::unchecked(WRITER),
+        expiration: Expiration::AtTime(mock_env().block.time),
+        vault_addr: Addr::unchecked("vault_addr"),
+        strike: Uint128::new(1_000_000),
+        bid: Uint128::zero(),
+        bidder: None,
+        settled: false,
+        exercise_style: ExerciseStyle::European,
+        auction_end: Expiration::AtTime(mock_env().block.time),
+    }
+    .save(deps.as_mut().storage, &1)
+    .unwrap();
+
+    let res = contract::query(
+        deps.as_ref(),
+        mock_env(),
+        cw721_base::QueryMsg::Extension {
+            msg: CallInstrumentQueryMsg::SettlementPreview { option_id: 1 },
+        },
+    );
+    assert_eq!(res, Err(ContractError::NoWinningBidder {}));
+}
+
+#[test]
+fn settlement_breakdown_pays_royalty() {
+    let call = CallInstrument {
+        asset_id: NFT_ID.to_string(),
+        writer_addr: Addr::unchecked(WRITER),
+        expiration: Expiration::AtTime(mock_env().block.time),
+        vault_addr: Addr::unchecked("vault_addr"),
+        strike: Uint128::new(1_000_000),
+        bid: Uint128::new(1_100_000),
+        bidder: Some(Addr::unchecked(USER)),
+        settled: false,
+        exercise_style: ExerciseStyle::European,
+        auction_end: Expiration::AtTime(mock_env().block.time),
+    };
+    let royalty = |amount| common::nft::RoyaltiesInfoResponse {
+        address: "creator".to_string(),
+        royalty_amount: Uint128::new(amount),
+    };
+
+    // the royalty is paid out of the spread
+    let breakdown = crate::utils::settlement_breakdown(
+        &call,
+        &protocol::state::Config::default(),
+        Some(royalty(55_000)),
+        Uint128::zero(),
+    )
+    .unwrap();
+    assert_eq!(breakdown.royalty_amount, Uint128::new(55_000));
+    assert_eq!(
+        breakdown.royalty_recipient,
+        Some(Addr::unchecked("creator"))
+    );
+    assert_eq!(breakdown.holder_amount, Uint128::new(45_000));
+    assert_eq!(breakdown.writer_amount, Uint128::new(1_000_000));
+
+    // a royalty larger than the spread is capped at it, the writer still receives the strike
+    let breakdown = crate::utils::settlement_breakdown(
+        &call,
+        &protocol::state::Config::default(),
+        Some(royalty(1_100_000)),
+        Uint128::zero(),
+    )
+    .unwrap();
+    assert_eq!(breakdown.royalty_amount, Uint128::new(100_000));
+    assert_eq!(breakdown.holder_amount, Uint128::zero());
+    assert_eq!(breakdown.writer_amount, Uint128::new(1_000_000));
+}
+
+#[test]
+fn cant_bid_while_paused() {
+    let (mut deps, _) = setup(USER, &[]);
+    mock_protocol(&mut deps, true);
+
+    let msg = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::Bid { option_id: 1 },
+    };
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &coins(10, "ATOM")),
+        msg,
+    );
+
+    assert_eq!(res, Err(ContractError::ProtocolPaused {}));
+
+    // cw20 bids are rejected the same way, while exercising stays available
+    let receive = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, msg: &ReceiveMsg| {
+        contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("cw20_addr", &[]),
+            cw721_base::ExecuteMsg::Extension {
+                msg: crate::ExecuteMsg::Receive(cw20::Cw20ReceiveMsg {
+                    sender: USER.to_string(),
+                    amount: Uint128::new(10),
+                    msg: to_binary(msg).unwrap(),
+                }),
+            },
+        )
+    };
+    let res = receive(&mut deps, &ReceiveMsg::Bid { option_id: 1 });
+    assert_eq!(res, Err(ContractError::ProtocolPaused {}));
+    let res = receive(&mut deps, &ReceiveMsg::Exercise { option_id: 1 });
+    assert_eq!(res, Err(ContractError::DenomNotAllowed {}));
+}
+
+#[test]
+fn failed_refund_can_be_withdrawn() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    // two refunds sent in the same transaction, each replies with its own id in any order
+    let config = Config::load(deps.as_ref().storage).unwrap();
+    let refunds = [("other_addr", 50), (USER, 100)].map(|(bidder, amount)| {
+        crate::execute::refund_msg(
+            deps.as_mut().storage,
+            &config,
+            Addr::unchecked(bidder),
+            Uint128::new(amount),
+        )
+        .unwrap()
+    });
+    assert_eq!(
+        refunds.iter().map(|refund| refund.id).collect::<Vec<_>>(),
+        vec![
+            crate::execute::REFUND_REPLY_ID,
+            crate::execute::REFUND_REPLY_ID + 1
+        ]
+    );
+
+    // only the failed one is credited
+    let failed = Reply {
+        id: refunds[1].id,
+        result: SubMsgResult::Err("blocked address".to_string()),
+    };
+    contract::reply(deps.as_mut(), mock_env(), failed.clone()).unwrap();
+    let sent = Reply {
+        id: refunds[0].id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    contract::reply(deps.as_mut(), mock_env(), sent).unwrap();
+    assert!(REFUNDS_IN_FLIGHT.is_empty(deps.as_ref().storage));
+    assert!(!PENDING_REFUNDS.has(deps.as_ref().storage, &Addr::unchecked("other_addr")));
+
+    // a refund replies only once
+    let res = contract::reply(deps.as_mut(), mock_env(), failed);
+    assert_eq!(res, Err(ContractError::UnknownReplyID {}));
+
+    let pending_refunds = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| -> Uint128 {
+        from_binary(
+            &contract::query(
+                deps.as_ref(),
+                mock_env(),
+                cw721_base::QueryMsg::Extension {
+                    msg: CallInstrumentQueryMsg::PendingRefunds {
+                        address: USER.to_string(),
+                        denom: None,
+                    },
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+    assert_eq!(pending_refunds(&deps), Uint128::new(100));
+
+    let msg = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::WithdrawRefunds {},
+    };
+    let res = contract::execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), msg).unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: USER.to_string(),
+            amount: coins(100, "ATOM"),
+        })]
+    );
+    assert_eq!(pending_refunds(&deps), Uint128::zero());
+}
+
+#[test]
+fn holder_exercises_american_option() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    let expiration = Expiration::AtTime(mock_env().block.time.plus_seconds(2 * DAY));
+    for (option_id, exercise_style) in [(1, ExerciseStyle::European), (2, ExerciseStyle::American)]
+    {
+        CallInstrument {
+            asset_id: NFT_ID.to_string(),
+            writer_addr: Addr::unchecked(WRITER),
+            expiration,
+            vault_addr: Addr::unchecked("vault_addr"),
+            strike: Uint128::new(5),
+            bid: Uint128::zero(),
+            bidder: None,
+            settled: false,
+            exercise_style,
+            auction_end: expiration,
+        }
+        .save(deps.as_mut().storage, &option_id)
+        .unwrap();
+        let extension = option_metadata(&deps, option_id);
+        CallInstrumentContract::default()
+            .tokens
+            .save(
+                deps.as_mut().storage,
+                &option_id.to_string(),
+                &cw721_base::state::TokenInfo {
+                    owner: Addr::unchecked(USER),
+                    approvals: vec![],
+                    token_uri: None,
+                    extension,
+                },
+            )
+            .unwrap();
+        CallInstrumentContract::default()
+            .increment_tokens(deps.as_mut().storage)
+            .unwrap();
+    }
+
+    let exercise = |option_id| cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::Exercise { option_id },
+    };
+
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &coins(5, "ATOM")),
+        exercise(1),
+    );
+    assert_eq!(res, Err(ContractError::NotAmericanOption {}));
+
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &coins(4, "ATOM")),
+        exercise(2),
+    );
+    assert_eq!(res, Err(ContractError::InvalidStrikeDeposit {}));
+
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &coins(5, "ATOM")),
+        exercise(2),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(
+        res.messages[2],
+        SubMsg::new(BankMsg::Send {
+            to_address: WRITER.to_string(),
+            amount: coins(5, "ATOM"),
+        })
+    );
+    assert!(CallInstrument::load(&deps.storage, &2).unwrap().settled);
+    assert!(!CallInstrumentContract::default()
+        .tokens
+        .has(&deps.storage, "2"));
+}
+
+#[test]
+fn nft_info_returns_option_metadata() {
+    let (mut deps, _) = setup(USER, &[]);
+    let config = Config::load(deps.as_ref().storage).unwrap();
+
+    let env = mock_env();
+    let expiration = Expiration::AtTime(env.block.time.plus_seconds(2 * DAY));
+    let option_id = crate::utils::mint_call(
+        deps.as_mut(),
+        &env,
+        WRITER,
+        "vault_addr",
+        &NFT_ID.to_string(),
+        Uint128::new(500_000),
+        expiration,
+        ExerciseStyle::American,
+        &config,
+    )
+    .unwrap();
+
+    let nft_info = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        from_binary::<cw721::NftInfoResponse<OptionMetadata>>(
+            &contract::query(
+                deps.as_ref(),
+                mock_env(),
+                cw721_base::QueryMsg::NftInfo {
+                    token_id: option_id.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .extension
+    };
+    assert_eq!(
+        nft_info(&deps),
+        OptionMetadata {
+            underlying_nft: Addr::unchecked(NFT_ADDR),
+            asset_id: NFT_ID.to_string(),
+            vault_addr: Addr::unchecked("vault_addr"),
+            strike: Uint128::new(500_000),
+            denom: Denom::from("ATOM"),
+            expiration,
+            exercise_style: ExerciseStyle::American,
+            bid: Uint128::zero(),
+            settled: false,
+        }
+    );
+
+    let mut call = CallInstrument::load(deps.as_ref().storage, &option_id).unwrap();
+    call.bid = Uint128::new(600_000);
+    call.bidder = Some(Addr::unchecked(USER));
+    call.save(deps.as_mut().storage, &option_id).unwrap();
+    crate::utils::sync_option_metadata(deps.as_mut().storage, &option_id, &call, &config).unwrap();
+
+    assert_eq!(nft_info(&deps).bid, Uint128::new(600_000));
+}
+
+#[test]
+fn nft_info_serves_svg_token_uri() {
+    let (mut deps, _) = setup(USER, &[]);
+    let config = Config::load(deps.as_ref().storage).unwrap();
+
+    let env = mock_env();
+    let option_id = crate::utils::mint_call(
+        deps.as_mut(),
+        &env,
+        WRITER,
+        "vault_addr",
+        &"<id&1>".to_string(),
+        Uint128::new(500_000),
+        Expiration::AtTime(env.block.time.plus_seconds(2 * DAY)),
+        ExerciseStyle::European,
+        &config,
+    )
+    .unwrap();
+
+    let nft_info: cw721::NftInfoResponse<OptionMetadata> = from_binary(
+        &contract::query(
+            deps.as_ref(),
+            mock_env(),
+            cw721_base::QueryMsg::NftInfo {
+                token_id: option_id.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let all_nft_info: cw721::AllNftInfoResponse<OptionMetadata> = from_binary(
+        &contract::query(
+            deps.as_ref(),
+            mock_env(),
+            cw721_base::QueryMsg::AllNftInfo {
+                token_id: option_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(all_nft_info.info, nft_info);
+
+    let token_uri = nft_info.token_uri.unwrap();
+    let svg = token_uri
+        .strip_prefix("data:image/svg+xml;base64,")
+        .unwrap();
+    let svg = String::from_utf8(Binary::from_base64(svg).unwrap().to_vec()).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("Collection: bayc_nft_addr"));
+    assert!(svg.contains("Token: #&lt;id&amp;1&gt;"));
+    assert!(svg.contains("Strike: 500000 ATOM"));
+    assert!(svg.contains("High bid: 0 ATOM"));
+}
+
+#[test]
+fn batch_mint_with_nft_queries_operators_and_vault_once() {
+    let (mut deps, _) = setup(WRITER, &[]);
+
+    // counts the operator and vault lookups of the batch
+    let lookups = std::rc::Rc::new(std::cell::Cell::new((0, 0)));
+    let counter = lookups.clone();
+    deps.querier.update_wasm(move |query| {
+        let WasmQuery::Smart { contract_addr, msg } = query else {
+            unreachable!()
+        };
+        let response = match contract_addr.as_str() {
+            NFT_ADDR => match from_binary(msg).unwrap() {
+                cw721::Cw721QueryMsg::OwnerOf { .. } => to_binary(&cw721::OwnerOfResponse {
+                    owner: WRITER.to_string(),
+                    approvals: vec![],
+                }),
+                cw721::Cw721QueryMsg::AllOperators { .. } => {
+                    let (operators, vaults) = counter.get();
+                    counter.set((operators + 1, vaults));
+                    to_binary(&cw721::OperatorsResponse {
+                        operators: vec![cw721::Approval {
+                            spender: MOCK_CONTRACT_ADDR.to_string(),
+                            expires: cw721::Expiration::Never {},
+                        }],
+                    })
+                }
+                cw721::Cw721QueryMsg::Approvals { .. } => {
+                    to_binary(&cw721::ApprovalsResponse { approvals: vec![] })
+                }
+                _ => unreachable!(),
+            },
+            "vault_factory_addr" => {
+                let (operators, vaults) = counter.get();
+                counter.set((operators, vaults + 1));
+                to_binary(&Some(Addr::unchecked("vault_addr")))
+            }
+            _ => to_binary(&false),
+        };
+        SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+    });
+
+    let expiration = Expiration::AtTime(mock_env().block.time.plus_seconds(DAY));
+    let mint_batch = |asset_ids: &[&str]| cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::MintBatchWithNFT {
+            nft_addr: NFT_ADDR.to_string(),
+            entries: asset_ids
+                .iter()
+                .map(|asset_id| MintBatchEntry {
+                    asset_id: asset_id.to_string(),
+                    strike: Uint128::new(500_000),
+                    expiration,
+                })
+                .collect(),
+            exercise_style: None,
+        },
+    };
+
+    // an asset can only be written once in a batch, nothing is queried before rejecting it
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(WRITER, &[]),
+        mint_batch(&["id001", "id002", "id001"]),
+    );
+    assert_eq!(res, Err(ContractError::DuplicateAsset("id001".to_string())));
+    assert_eq!(lookups.get(), (0, 0));
+
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(WRITER, &[]),
+        mint_batch(&["id001", "id002", "id003"]),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(lookups.get(), (1, 1));
+}
+
+#[test]
+fn writer_amends_and_rolls_unsold_option() {
+    let (mut deps, _) = setup(USER, &[]);
+    let config = Config::load(deps.as_ref().storage).unwrap();
+
+    let env = mock_env();
+    let option_id = crate::utils::mint_call(
+        deps.as_mut(),
+        &env,
+        WRITER,
+        "vault_addr",
+        &NFT_ID.to_string(),
+        Uint128::new(500_000),
+        Expiration::AtTime(env.block.time.plus_seconds(2 * DAY)),
+        ExerciseStyle::European,
+        &config,
+    )
+    .unwrap();
+
+    let new_expiration = Expiration::AtTime(env.block.time.plus_seconds(7 * DAY));
+    let amend = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::AmendOption {
+            option_id,
+            new_strike: Uint128::new(800_000),
+            new_expiration,
+        },
+    };
+
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        amend.clone(),
+    );
+    assert_eq!(res, Err(ContractError::Unauthorized {}));
+
+    // a listing made on the previous terms is cancelled, its best bid becomes a pending refund
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(WRITER, &[]),
+        cw721_base::ExecuteMsg::Extension {
+            msg: crate::ExecuteMsg::ListOption {
+                option_id,
+                premium: Uint128::new(300),
+                expires: Expiration::AtTime(env.block.time.plus_seconds(DAY)),
+                kind: Some(ListingKind::Auction),
+            },
+        },
+    )
+    .unwrap();
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &coins(300, "ATOM")),
+        cw721_base::ExecuteMsg::Extension {
+            msg: crate::ExecuteMsg::BuyOption { option_id },
+        },
+    )
+    .unwrap();
+
+    let res = contract::execute(deps.as_mut(), mock_env(), mock_info(WRITER, &[]), amend).unwrap();
+    assert!(!LISTINGS.has(deps.as_ref().storage, &option_id));
+    assert_eq!(
+        PENDING_REFUNDS
+            .load(deps.as_ref().storage, &Addr::unchecked("carol"))
+            .unwrap(),
+        vec![(Denom::from("ATOM"), Uint128::new(300))]
+    );
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "option_listing_cancelled"));
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(vault::utils::clear_entitlement_wasm_msg("vault_addr", NFT_ID).unwrap()),
+            SubMsg::new(
+                vault::utils::impose_entitlement_wasm_msg(
+                    "vault_addr",
+                    NFT_ID,
+                    env.contract.address.as_str(),
+                    new_expiration,
+                )
+                .unwrap()
+            ),
+        ]
+    );
+    let call = CallInstrument::load(deps.as_ref().storage, &option_id).unwrap();
+    assert_eq!(call.strike, Uint128::new(800_000));
+    assert_eq!(call.expiration, new_expiration);
+    assert_eq!(
+        option_metadata(&deps, option_id).strike,
+        Uint128::new(800_000)
+    );
+
+    // the rolled option keeps the terms it was written under, also after a config update
+    let mut updated_config = Config::load(deps.as_ref().storage).unwrap();
+    updated_config.allowed_denom = Denom::from("uosmo");
+    updated_config.save(deps.as_mut().storage).unwrap();
+
+    let roll = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::RollOption {
+            option_id,
+            new_strike: Uint128::new(900_000),
+            new_expiration,
+        },
+    };
+    let res = contract::execute(deps.as_mut(), mock_env(), mock_info(WRITER, &[]), roll).unwrap();
+    assert_eq!(res.messages.len(), 2);
+
+    let new_option_id = option_id + 1;
+    assert!(
+        CallInstrument::load(deps.as_ref().storage, &option_id)
+            .unwrap()
+            .settled
+    );
+    assert!(CallInstrumentContract::default()
+        .tokens
+        .may_load(deps.as_ref().storage, &option_id.to_string())
+        .unwrap()
+        .is_none());
+
+    let new_call = CallInstrument::load(deps.as_ref().storage, &new_option_id).unwrap();
+    assert_eq!(new_call.strike, Uint128::new(900_000));
+    assert_eq!(new_call.writer_addr, Addr::unchecked(WRITER));
+    assert_eq!(
+        OPTION_TERMS
+            .load(deps.as_ref().storage, &new_option_id)
+            .unwrap()
+            .allowed_denom,
+        Denom::from("ATOM")
+    );
+    assert_eq!(
+        CallInstrumentContract::default()
+            .tokens
+            .load(deps.as_ref().storage, &new_option_id.to_string())
+            .unwrap()
+            .owner,
+        Addr::unchecked(WRITER)
+    );
+
+    // options with bids can't be amended
+    let mut new_call = new_call;
+    new_call.bid = Uint128::new(900_000);
+    new_call.bidder = Some(Addr::unchecked(USER));
+    new_call
+        .save(deps.as_mut().storage, &new_option_id)
+        .unwrap();
+    let amend = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::AmendOption {
+            option_id: new_option_id,
+            new_strike: Uint128::new(1_000_000),
+            new_expiration,
+        },
+    };
+    let res = contract::execute(deps.as_mut(), mock_env(), mock_info(WRITER, &[]), amend);
+    assert_eq!(res, Err(ContractError::OptionHasBids {}));
+}
+
+#[test]
+fn options_with_committed_bids_cant_be_amended_or_rolled() {
+    let (mut deps, _) = setup(USER, &[]);
+    let mut config = Config::load(deps.as_ref().storage).unwrap();
+    config.auction_mode = AuctionMode::SealedBid {
+        reveal_period: DAY,
+        second_price: false,
+        unrevealed_deposits: UnrevealedDeposits::ForfeitToWriter,
+    };
+    config.save(deps.as_mut().storage).unwrap();
+
+    let mut env = mock_env();
+    let expiration = Expiration::AtTime(env.block.time.plus_seconds(DAY));
+    let option_id = crate::utils::mint_call(
+        deps.as_mut(),
+        &env,
+        WRITER,
+        "vault_addr",
+        &NFT_ID.to_string(),
+        Uint128::new(500_000),
+        expiration,
+        ExerciseStyle::European,
+        &config,
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(DAY - HOUR);
+    contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &coins(1_000_000, "ATOM")),
+        cw721_base::ExecuteMsg::Extension {
+            msg: crate::ExecuteMsg::CommitBid {
+                option_id,
+                commitment: crate::utils::bid_commitment(
+                    &Addr::unchecked("alice"),
+                    &option_id,
+                    Uint128::new(800_000),
+                    "salt",
+                ),
+            },
+        },
+    )
+    .unwrap();
+
+    // the committed deposit can't be forfeited by replacing the option, nor its terms changed
+    let new_expiration = Expiration::AtTime(env.block.time.plus_seconds(7 * DAY));
+    for msg in [
+        crate::ExecuteMsg::AmendOption {
+            option_id,
+            new_strike: Uint128::new(900_000),
+            new_expiration,
+        },
+        crate::ExecuteMsg::RollOption {
+            option_id,
+            new_strike: Uint128::new(900_000),
+            new_expiration,
+        },
+    ] {
+        let res = contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(WRITER, &[]),
+            cw721_base::ExecuteMsg::Extension { msg },
+        );
+        assert_eq!(res, Err(ContractError::OptionHasBids {}));
+    }
+    let call = CallInstrument::load(deps.as_ref().storage, &option_id).unwrap();
+    assert_eq!(call.strike, Uint128::new(500_000));
+    assert!(!call.settled);
+}
+
+#[test]
+fn sealed_bid_auction_settles_at_second_price() {
+    let (mut deps, _) = setup(USER, &[]);
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            unreachable!()
+        };
+        let response = match from_binary(msg) {
+            Ok(protocol::msg::QueryMsg::Config {}) => {
+                to_binary(&protocol::state::Config::default())
+            }
+            _ => to_binary(&false),
+        };
+        SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+    });
+
+    let mut config = Config::load(deps.as_ref().storage).unwrap();
+    config.auction_mode = AuctionMode::SealedBid {
+        reveal_period: DAY,
+        second_price: true,
+        unrevealed_deposits: UnrevealedDeposits::ForfeitToWriter,
+    };
+    config.save(deps.as_mut().storage).unwrap();
+
+    let mut env = mock_env();
+    let expiration = Expiration::AtTime(env.block.time.plus_seconds(DAY / 2));
+    let option_id = crate::utils::mint_call(
+        deps.as_mut(),
+        &env,
+        WRITER,
+        "vault_addr",
+        &NFT_ID.to_string(),
+        Uint128::new(500_000),
+        expiration,
+        ExerciseStyle::European,
+        &config,
+    )
+    .unwrap();
+
+    let execute = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   env: &Env,
+                   sender: &str,
+                   funds: u128,
+                   msg: crate::ExecuteMsg| {
+        let funds = if funds > 0 {
+            coins(funds, "ATOM")
+        } else {
+            vec![]
+        };
+        contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(sender, &funds),
+            cw721_base::ExecuteMsg::Extension { msg },
+        )
+    };
+    let commit = |bidder: &str, amount: u128| crate::ExecuteMsg::CommitBid {
+        option_id,
+        commitment: crate::utils::bid_commitment(
+            &Addr::unchecked(bidder),
+            &option_id,
+            Uint128::new(amount),
+            "salt",
+        ),
+    };
+    let reveal = |amount: u128, salt: &str| crate::ExecuteMsg::RevealBid {
+        option_id,
+        amount: Uint128::new(amount),
+        salt: salt.to_string(),
+    };
+    let release = |bidder: &str| crate::ExecuteMsg::ReleaseDeposit {
+        option_id,
+        bidder: bidder.to_string(),
+    };
+
+    // bids are committed once the settlement auction starts, open bids are not accepted
+    env.block.time = env.block.time.plus_seconds(DAY / 2 - HOUR);
+    let res = execute(
+        &mut deps,
+        &env,
+        "alice",
+        1_000_000,
+        crate::ExecuteMsg::Bid { option_id },
+    );
+    assert_eq!(res, Err(ContractError::SealedBidAuction {}));
+
+    execute(
+        &mut deps,
+        &env,
+        "alice",
+        2_000_000,
+        commit("alice", 1_500_000),
+    )
+    .unwrap();
+    execute(&mut deps, &env, "bob", 1_000_000, commit("bob", 800_000)).unwrap();
+    execute(&mut deps, &env, "carol", 900_000, commit("carol", 900_000)).unwrap();
+    let res = execute(&mut deps, &env, "carol", 900_000, commit("carol", 900_000));
+    assert_eq!(res, Err(ContractError::BidAlreadyCommitted {}));
+
+    // bids are revealed after expiration
+    let res = execute(&mut deps, &env, "alice", 0, reveal(1_500_000, "salt"));
+    assert_eq!(res, Err(ContractError::OptionNotExpired(option_id)));
+
+    env.block.time = env.block.time.plus_seconds(HOUR + 1);
+    let res = execute(&mut deps, &env, "alice", 0, reveal(1_500_000, "wrong"));
+    assert_eq!(res, Err(ContractError::CommitmentMismatch {}));
+    execute(&mut deps, &env, "alice", 0, reveal(1_500_000, "salt")).unwrap();
+    let res = execute(&mut deps, &env, "alice", 0, reveal(1_500_000, "salt"));
+    assert_eq!(res, Err(ContractError::BidAlreadyRevealed {}));
+    let res = execute(&mut deps, &env, "dave", 0, reveal(1_500_000, "salt"));
+    assert_eq!(res, Err(ContractError::NoCommittedBid {}));
+    execute(&mut deps, &env, "bob", 0, reveal(800_000, "salt")).unwrap();
+
+    let call = CallInstrument::load(deps.as_ref().storage, &option_id).unwrap();
+    assert_eq!(call.bidder, Some(Addr::unchecked("alice")));
+    assert_eq!(call.bid, Uint128::new(1_500_000));
+
+    // the outbid deposit is released right away, unrevealed ones after the reveal period
+    let res = execute(&mut deps, &env, USER, 0, release("bob")).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            BankMsg::Send {
+                to_address: "bob".to_string(),
+                amount: coins(1_000_000, "ATOM"),
+            },
+            crate::execute::REFUND_REPLY_ID,
+        )]
+    );
+    let res = execute(&mut deps, &env, USER, 0, release("carol"));
+    assert_eq!(res, Err(ContractError::RevealPeriodNotOver {}));
+    let res = execute(&mut deps, &env, USER, 0, release("alice"));
+    assert_eq!(res, Err(ContractError::WinningDepositLocked {}));
+
+    // the winner pays the second price and gets the rest of the deposit back,
+    // replied with the next id as the refund of bob is still in flight
+    env.block.time = env.block.time.plus_seconds(DAY);
+    let res = execute(
+        &mut deps,
+        &env,
+        USER,
+        0,
+        crate::ExecuteMsg::SettleOption { option_id },
+    )
+    .unwrap();
+    assert!(res.messages.contains(&SubMsg::reply_always(
+        BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(1_200_000, "ATOM"),
+        },
+        crate::execute::REFUND_REPLY_ID + 1,
+    )));
+    let call = CallInstrument::load(deps.as_ref().storage, &option_id).unwrap();
+    assert_eq!(call.bid, Uint128::new(800_000));
+    assert!(call.settled);
+
+    // the unrevealed deposit is forfeited to the writer
+    let res = execute(&mut deps, &env, USER, 0, release("carol")).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: WRITER.to_string(),
+            amount: coins(900_000, "ATOM"),
+        })]
+    );
+}
+
+#[test]
+fn late_bids_extend_the_auction() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    let mut config = Config::load(deps.as_ref().storage).unwrap();
+    config.anti_snipe = Some(AntiSnipe {
+        window: 600,
+        extension: 600,
+        max_extension: 900,
+    });
+    config.save(deps.as_mut().storage).unwrap();
+
+    let mut env = mock_env();
+    let expires_at = env.block.time.plus_seconds(DAY);
+    let option_id = crate::utils::mint_call(
+        deps.as_mut(),
+        &env,
+        WRITER,
+        "vault_addr",
+        &NFT_ID.to_string(),
+        Uint128::new(500_000),
+        Expiration::AtTime(expires_at),
+        ExerciseStyle::European,
+        &config,
+    )
+    .unwrap();
+
+    let bid = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, env: &Env, amount| {
+        contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER, &coins(amount, "ATOM")),
+            cw721_base::ExecuteMsg::Extension {
+                msg: crate::ExecuteMsg::Bid { option_id },
+            },
+        )
+    };
+    let auction_end = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        CallInstrument::load(deps.as_ref().storage, &option_id)
+            .unwrap()
+            .auction_end
+    };
+
+    // a bid within the window extends the auction and the entitlement
+    env.block.time = expires_at.minus_seconds(300);
+    let res = bid(&mut deps, &env, 1_000_000).unwrap();
+    let extended = Expiration::AtTime(expires_at.plus_seconds(600));
+    assert_eq!(auction_end(&deps), extended);
+    assert!(res.messages.contains(&SubMsg::new(
+        vault::utils::impose_entitlement_wasm_msg(
+            "vault_addr",
+            NFT_ID,
+            env.contract.address.as_str(),
+            extended,
+        )
+        .unwrap()
+    )));
+
+    assert_eq!(
+        bid(&mut deps, &env, 1_005_000),
+        Err(ContractError::BidIncrementTooSmall {
+            min_bid: Uint128::new(1_010_000)
+        })
+    );
+
+    // bids are accepted after expiration until the auction end, extensions are capped
+    env.block.time = expires_at.plus_seconds(100);
+    let res = bid(&mut deps, &env, 1_100_000).unwrap();
+    assert_eq!(
+        auction_end(&deps),
+        Expiration::AtTime(expires_at.plus_seconds(900))
+    );
+    assert_eq!(
+        res.events,
+        vec![
+            Event::from(BidRefunded {
+                option_id,
+                bidder: Addr::unchecked(USER),
+                amount: Uint128::new(1_000_000),
+                denom: Denom::from("ATOM"),
+            }),
+            Event::from(BidPlaced {
+                option_id,
+                bidder: Addr::unchecked(USER),
+                amount: Uint128::new(1_100_000),
+                denom: Denom::from("ATOM"),
+                auction_end: Expiration::AtTime(expires_at.plus_seconds(900)),
+            }),
+        ]
+    );
+
+    let res = contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(USER, &[]),
+        cw721_base::ExecuteMsg::Extension {
+            msg: crate::ExecuteMsg::SettleOption { option_id },
+        },
+    );
+    assert_eq!(res, Err(ContractError::OptionNotExpired(option_id)));
+
+    env.block.time = expires_at.plus_seconds(901);
+    let res = bid(&mut deps, &env, 1_200_000);
+    assert_eq!(res, Err(ContractError::OptionIsExpired {}));
+}
+
+#[test]
+fn existing_options_keep_their_terms_after_config_update() {
+    let (mut deps, _) = setup(USER, &[]);
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            unreachable!()
+        };
+        let response = match from_binary(msg) {
+            Ok(protocol::msg::QueryMsg::Ownership {}) => to_binary(&cw_ownable::Ownership {
+                owner: Some(Addr::unchecked("protocol_owner")),
+                pending_owner: None,
+                pending_expiry: None,
+            }),
+            _ => to_binary(&false),
+        };
+        SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+    });
+
+    let env = mock_env();
+    let mint = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        let config = Config::load(deps.as_ref().storage).unwrap();
+        crate::utils::mint_call(
+            deps.as_mut(),
+            &env,
+            WRITER,
+            "vault_addr",
+            &NFT_ID.to_string(),
+            Uint128::new(500_000),
+            Expiration::AtTime(env.block.time.plus_seconds(DAY)),
+            ExerciseStyle::European,
+            &config,
+        )
+        .unwrap()
+    };
+    let option_id = mint(&mut deps);
+
+    let update_config = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::UpdateConfig {
+            expiry_range: None,
+            expiry_series: None,
+            clear_expiry_series: None,
+            allowed_denom: Some(Denom::from("uosmo")),
+            min_bid_increment_bps: Some(500),
+            settlement_auction_start_offset: None,
+            royalties_enabled: None,
+            keeper_bounty: None,
+        },
+    };
+    let res = contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(USER, &[]),
+        update_config.clone(),
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::OwnershipError(
+            cw_ownable::OwnershipError::NotOwner
+        ))
+    );
+    contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("protocol_owner", &[]),
+        update_config,
+    )
+    .unwrap();
+
+    let config = Config::load(&deps.storage).unwrap();
+    assert_eq!(config.allowed_denom, Denom::from("uosmo"));
+    assert_eq!(config.min_bid_increment_bps, 500);
+
+    // the existing option is still bid in the denom it was written under, once its auction starts
+    let mut bid_env = env.clone();
+    bid_env.block.time = env.block.time.plus_seconds(DAY - HOUR);
+    let bid =
+        |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, option_id, funds: Vec<Coin>| {
+            contract::execute(
+                deps.as_mut(),
+                bid_env.clone(),
+                mock_info(USER, &funds),
+                cw721_base::ExecuteMsg::Extension {
+                    msg: crate::ExecuteMsg::Bid { option_id },
+                },
+            )
+        };
+    let res = bid(&mut deps, option_id, coins(1_000_000, "uosmo"));
+    assert_eq!(res, Err(ContractError::DenomNotAllowed {}));
+    bid(&mut deps, option_id, coins(1_000_000, "ATOM")).unwrap();
+
+    let terms: OptionTerms = from_binary(
+        &contract::query(
+            deps.as_ref(),
+            env.clone(),
+            cw721_base::QueryMsg::Extension {
+                msg: CallInstrumentQueryMsg::OptionTerms { option_id },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(terms.allowed_denom, Denom::from("ATOM"));
+    assert_eq!(terms.min_bid_increment_bps, 100);
+
+    // new options are written under the updated config, once the asset is free again
+    let mut call = CallInstrument::load(&deps.storage, &option_id).unwrap();
+    call.settled = true;
+    call.save(deps.as_mut().storage, &option_id).unwrap();
+    let new_option_id = mint(&mut deps);
+    let res = bid(&mut deps, new_option_id, coins(1_000_000, "ATOM"));
+    assert_eq!(res, Err(ContractError::DenomNotAllowed {}));
+    bid(&mut deps, new_option_id, coins(1_000_000, "uosmo")).unwrap();
+
+    // an expiry series can be set and removed again
+    let update_series = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                         expiry_series,
+                         clear_expiry_series| {
+        contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("protocol_owner", &[]),
+            cw721_base::ExecuteMsg::Extension {
+                msg: crate::ExecuteMsg::UpdateConfig {
+                    expiry_range: None,
+                    expiry_series,
+                    clear_expiry_series,
+                    allowed_denom: None,
+                    min_bid_increment_bps: None,
+                    settlement_auction_start_offset: None,
+                    royalties_enabled: None,
+                    keeper_bounty: None,
+                },
+            },
+        )
+    };
+    let series = ExpirySeries::Weekly {
+        weekday: 4,
+        hour: 8,
+    };
+    update_series(&mut deps, Some(series.clone()), None).unwrap();
+    assert_eq!(
+        Config::load(&deps.storage).unwrap().expiry_series,
+        Some(series.clone())
+    );
+    let res = update_series(&mut deps, Some(series), Some(true));
+    assert_eq!(res, Err(ContractError::InvalidConfig {}));
+    update_series(&mut deps, None, Some(true)).unwrap();
+    assert_eq!(Config::load(&deps.storage).unwrap().expiry_series, None);
+}
+
+#[test]
+fn expiration_must_be_a_time_within_the_range() {
+    let (mut deps, _) = setup(USER, &[]);
+    let env = mock_env();
+    let config = Config::load(deps.as_ref().storage).unwrap();
+
+    let mut mint = |expiration| {
+        crate::utils::mint_call(
+            deps.as_mut(),
+            &env,
+            WRITER,
+            "vault_addr",
+            &NFT_ID.to_string(),
+            Uint128::new(500_000),
+            expiration,
+            ExerciseStyle::European,
+            &config,
+        )
+    };
+
+    assert_eq!(
+        mint(Expiration::Never {}),
+        Err(ContractError::ExpirationNotTime {})
+    );
+    assert_eq!(
+        mint(Expiration::AtHeight(env.block.height + 1_000)),
+        Err(ContractError::ExpirationNotTime {})
+    );
+    let out_of_range = Err(ContractError::OptionDurationOutOfRange {
+        min: 2 * HOUR,
+        max: 365 * DAY,
+    });
+    assert_eq!(mint(Expiration::AtTime(env.block.time)), out_of_range);
+    assert_eq!(
+        mint(Expiration::AtTime(
+            env.block.time.plus_seconds(365 * DAY + 1)
+        )),
+        out_of_range
+    );
+    assert!(mint(Expiration::AtTime(env.block.time.plus_seconds(365 * DAY))).is_ok());
+}
+
+#[test]
+fn options_are_written_on_the_expiry_series() {
+    let (mut deps, _) = setup(USER, &[]);
+    let env = mock_env();
+    let mut config = Config::load(deps.as_ref().storage).unwrap();
+    // fridays at 08:00 UTC, the block time is wednesday 2019-10-23
+    config.expiry_series = Some(ExpirySeries::Weekly {
+        weekday: 4,
+        hour: 8,
+    });
+    config.save(deps.as_mut().storage).unwrap();
+
+    let friday = Timestamp::from_seconds(1_571_990_400);
+    let mut mint = |asset_id: &str, expiration| {
+        crate::utils::mint_call(
+            deps.as_mut(),
+            &env,
+            WRITER,
+            "vault_addr",
+            &asset_id.to_string(),
+            Uint128::new(500_000),
+            Expiration::AtTime(expiration),
+            ExerciseStyle::European,
+            &config,
+        )
+    };
+
+    assert_eq!(
+        mint("id001", friday.plus_seconds(1)),
+        Err(ContractError::ExpirationNotInSeries {})
+    );
+    assert_eq!(mint("id001", friday), Ok(1));
+    assert_eq!(mint("id002", friday), Ok(2));
+    assert_eq!(mint("id003", friday.plus_seconds(7 * DAY)), Ok(3));
+
+    let query = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, msg| {
+        contract::query(
+            deps.as_ref(),
+            mock_env(),
+            cw721_base::QueryMsg::Extension { msg },
+        )
+        .unwrap()
+    };
+
+    let res: ActiveSeriesResponse = from_binary(&query(
+        &deps,
+        CallInstrumentQueryMsg::ActiveSeries {
+            start_after: None,
+            limit: Some(3),
+        },
+    ))
+    .unwrap();
+    assert_eq!(
+        res.series,
+        vec![
+            SeriesResponse {
+                expiration: friday,
+                open_options: 2,
+            },
+            SeriesResponse {
+                expiration: friday.plus_seconds(7 * DAY),
+                open_options: 1,
+            },
+            SeriesResponse {
+                expiration: friday.plus_seconds(14 * DAY),
+                open_options: 0,
+            },
+        ]
+    );
+
+    let res: ListOptionsResponse = from_binary(&query(
+        &deps,
+        CallInstrumentQueryMsg::SeriesOptions {
+            expiration: friday,
+            start_after: Some(1),
+            limit: None,
+        },
+    ))
+    .unwrap();
+    assert_eq!(
+        res.options.iter().map(|o| o.option_id).collect::<Vec<_>>(),
+        vec![2]
+    );
+
+    // monthly series roll over the end of the year
+    let monthly = ExpirySeries::Monthly { day: 1, hour: 8 };
+    assert_eq!(
+        monthly.next_after(env.block.time),
+        Timestamp::from_seconds(1_572_595_200)
+    );
+    assert_eq!(
+        monthly.next_after(Timestamp::from_seconds(1_576_368_000)),
+        Timestamp::from_seconds(1_577_865_600)
+    );
+    assert!(monthly.contains(Timestamp::from_seconds(1_577_865_600)));
+}
+
+#[test]
+fn migrate_from_v0_1() {
+    use crate::migrations::v0_1;
+
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+    let expiration = Expiration::AtTime(mock_env().block.time.plus_seconds(DAY));
+    v0_1::CONFIG
+        .save(
+            &mut deps.storage,
+            &v0_1::Config {
+                protocol_addr: Addr::unchecked("protocol_addr"),
+                allowed_underlying_nft: Addr::unchecked(NFT_ADDR),
+                vault_factory_addr: Addr::unchecked("vault_factory_addr"),
+                minimum_option_duration: 2 * DAY,
+                allowed_denom: "ATOM".to_string(),
+                min_bid_increment_bps: 100,
+            },
+        )
+        .unwrap();
+    v0_1::CALL_INSTRUMENTS
+        .save(
+            &mut deps.storage,
+            1,
+            &v0_1::CallInstrument {
+                asset_id: NFT_ID.to_string(),
+                writer_addr: Addr::unchecked(WRITER),
+                expiration,
+                vault_addr: Addr::unchecked("vault_addr"),
+                strike: Uint128::new(500_000),
+                bid: Uint128::zero(),
+                bidder: None,
+                settled: false,
+            },
+        )
+        .unwrap();
+    v0_1::TOKENS
+        .save(
+            &mut deps.storage,
+            "1",
+            &cw721_base::state::TokenInfo {
+                owner: Addr::unchecked(WRITER),
+                approvals: vec![],
+                token_uri: None,
+                extension: cosmwasm_std::Empty {},
+            },
+        )
+        .unwrap();
+
+    // the settlement auction offset and the maximum duration are required when migrating from 0.1
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            settlement_auction_start_offset: None,
+            maximum_option_duration: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidConfig {});
+
+    // the migrated config is validated, settlement auctions must start after options are written
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            settlement_auction_start_offset: Some(2 * DAY),
+            maximum_option_duration: Some(365 * DAY),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AuctionStartOffsetTooLong { min: 2 * DAY }
+    );
+
+    // the failed migrations are reverted on chain
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            settlement_auction_start_offset: Some(DAY),
+            maximum_option_duration: Some(365 * DAY),
+        },
+    )
+    .unwrap();
+
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, CONTRACT_VERSION);
+
+    let config = Config::load(&deps.storage).unwrap();
+    assert_eq!(config.allowed_denom, Denom::from("ATOM"));
+    assert_eq!(config.settlement_auction_start_offset, DAY);
+    assert_eq!(config.auction_mode, AuctionMode::Open);
+    assert_eq!(
+        config.expiry_range,
+        ExpiryRange {
+            min: 2 * DAY,
+            max: 365 * DAY
+        }
+    );
+
+    let call = CallInstrument::load(&deps.storage, &1).unwrap();
+    assert_eq!(call.exercise_style, ExerciseStyle::European);
+    assert_eq!(call.auction_end, expiration);
+    assert_eq!(
+        call_instruments()
+            .idx
+            .writer
+            .prefix(Addr::unchecked(WRITER))
+            .keys(&deps.storage, None, None, cosmwasm_std::Order::Ascending)
+            .collect::<Vec<_>>(),
+        vec![Ok(1)]
+    );
+
+    let token = CallInstrumentContract::default()
+        .tokens
+        .load(&deps.storage, "1")
+        .unwrap();
+    assert_eq!(token.owner, WRITER);
+    assert_eq!(token.extension, option_metadata(&deps, 1));
+
+    // downgrades are rejected
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "9.0.0").unwrap();
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            settlement_auction_start_offset: None,
+            maximum_option_duration: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidMigration {
+            contract: CONTRACT_NAME.to_string(),
+            version: "9.0.0".to_string(),
+        }
+    );
+}
+
+#[test]
+fn holder_sells_option_at_a_premium() {
+    let (mut deps, _) = setup(USER, &[]);
+    let config = Config::load(deps.as_ref().storage).unwrap();
+
+    let env = mock_env();
+    let expiration = Expiration::AtTime(env.block.time.plus_seconds(2 * DAY));
+    let mint = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, asset_id: &str| {
+        crate::utils::mint_call(
+            deps.as_mut(),
+            &env,
+            WRITER,
+            "vault_addr",
+            &asset_id.to_string(),
+            Uint128::new(500_000),
+            expiration,
+            ExerciseStyle::European,
+            &config,
+        )
+        .unwrap()
+    };
+    let list = |option_id, premium: u128, kind| cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::ListOption {
+            option_id,
+            premium: Uint128::new(premium),
+            expires: Expiration::AtTime(mock_env().block.time.plus_seconds(DAY)),
+            kind: Some(kind),
+        },
+    };
+    let buy = |option_id| cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::BuyOption { option_id },
+    };
+    let owner = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, option_id: u64| {
+        CallInstrumentContract::default()
+            .tokens
+            .load(deps.as_ref().storage, &option_id.to_string())
+            .unwrap()
+            .owner
+    };
+
+    let option_id = mint(&mut deps, NFT_ID);
+
+    // fixed price listings are bought at the exact premium
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        list(option_id, 100, ListingKind::FixedPrice),
+    );
+    assert_eq!(res, Err(ContractError::OnlyOptionOwner(USER.to_string())));
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(WRITER, &[]),
+        list(option_id, 100, ListingKind::FixedPrice),
+    )
+    .unwrap();
+
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &coins(90, "ATOM")),
+        buy(option_id),
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::InvalidPremium {
+            premium: Uint128::new(100)
+        })
+    );
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &coins(100, "ATOM")),
+        buy(option_id),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: WRITER.to_string(),
+            amount: coins(100, "ATOM"),
+        })]
+    );
+    assert_eq!(owner(&deps, option_id), USER);
+    assert!(!LISTINGS.has(deps.as_ref().storage, &option_id));
+
+    // premium auctions escrow the best bid until the listing is finalized
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        list(option_id, 200, ListingKind::Auction),
+    )
+    .unwrap();
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &coins(150, "ATOM")),
+        buy(option_id),
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::PremiumTooLow {
+            min_premium: Uint128::new(200)
+        })
+    );
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &coins(200, "ATOM")),
+        buy(option_id),
+    )
+    .unwrap();
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(201, "ATOM")),
+        buy(option_id),
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::PremiumTooLow {
+            min_premium: Uint128::new(202)
+        })
+    );
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(202, "ATOM")),
+        buy(option_id),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(200, "ATOM"),
+            },
+            crate::execute::REFUND_REPLY_ID,
+        )]
+    );
+    assert_eq!(owner(&deps, option_id), USER);
+
+    let finalize = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::FinalizeListing { option_id },
+    };
+    let res = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        finalize.clone(),
+    );
+    assert_eq!(res, Err(ContractError::ListingNotExpired {}));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(DAY + 1);
+    let res = contract::execute(deps.as_mut(), env, mock_info("keeper", &[]), finalize).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: USER.to_string(),
+            amount: coins(202, "ATOM"),
+        })]
+    );
+    assert_eq!(owner(&deps, option_id), "bob");
+
+    // the listing is cancelled when the writer reclaims the asset, the best bid becomes a pending refund
+    let option_id = mint(&mut deps, "id002");
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(WRITER, &[]),
+        list(option_id, 300, ListingKind::Auction),
+    )
+    .unwrap();
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &coins(300, "ATOM")),
+        buy(option_id),
+    )
+    .unwrap();
+
+    let reclaim = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::ReclaimAsset {
+            option_id,
+            withdraw: false,
+        },
+    };
+    contract::execute(deps.as_mut(), mock_env(), mock_info(WRITER, &[]), reclaim).unwrap();
+    assert!(!LISTINGS.has(deps.as_ref().storage, &option_id));
+    assert_eq!(
+        PENDING_REFUNDS
+            .load(deps.as_ref().storage, &Addr::unchecked("carol"))
+            .unwrap(),
+        vec![(Denom::from("ATOM"), Uint128::new(300))]
+    );
+}
+
+#[test]
+fn keepers_settle_expired_options_for_a_bounty() {
+    let (mut deps, _) = setup(USER, &[]);
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            unreachable!()
+        };
+        let response = match from_binary(msg) {
+            Ok(protocol::msg::QueryMsg::Config {}) => {
+                to_binary(&protocol::state::Config::default())
+            }
+            _ => to_binary(&false),
+        };
+        SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+    });
+
+    let mut config = Config::load(deps.as_ref().storage).unwrap();
+    config.keeper_bounty = Uint128::new(1_000);
+    config.save(deps.as_mut().storage).unwrap();
+
+    let env = mock_env();
+    let expiration = Expiration::AtTime(env.block.time.plus_seconds(DAY));
+    let option_ids = ["id001", "id002", "id003", "id004"].map(|asset_id| {
+        crate::utils::mint_call(
+            deps.as_mut(),
+            &env,
+            WRITER,
+            "vault_addr",
+            &asset_id.to_string(),
+            Uint128::new(500_000),
+            expiration,
+            ExerciseStyle::European,
+            &config,
+        )
+        .unwrap()
+    });
+    for option_id in &option_ids[..2] {
+        let mut call = CallInstrument::load(deps.as_ref().storage, option_id).unwrap();
+        call.bid = Uint128::new(600_000);
+        call.bidder = Some(Addr::unchecked("alice"));
+        call.save(deps.as_mut().storage, option_id).unwrap();
+    }
+
+    let execute = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   sender: &str,
+                   funds: &[Coin],
+                   msg: crate::ExecuteMsg| {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(DAY + 1);
+        contract::execute(
+            deps.as_mut(),
+            env,
+            mock_info(sender, funds),
+            cw721_base::ExecuteMsg::Extension { msg },
+        )
+    };
+    let bounty = SubMsg::new(BankMsg::Send {
+        to_address: "keeper".to_string(),
+        amount: coins(1_000, "ATOM"),
+    });
+
+    let res = execute(
+        &mut deps,
+        "keeper",
+        &[],
+        crate::ExecuteMsg::SettleBatch { option_ids: vec![] },
+    );
+    assert_eq!(res, Err(ContractError::InvalidBatchSize { max: 30 }));
+
+    // the bounty of each settled option is paid out of its spread, options without bids are skipped
+    let res = execute(
+        &mut deps,
+        "keeper",
+        &[],
+        crate::ExecuteMsg::SettleBatch {
+            option_ids: option_ids[..3].to_vec(),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.iter().filter(|msg| **msg == bounty).count(), 2);
+    let attribute = |res: &Response, key: &str| {
+        res.attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.clone())
+    };
+    assert_eq!(
+        attribute(&res, "option_ids"),
+        Some(format!("{},{}", option_ids[0], option_ids[1]))
+    );
+    assert_eq!(
+        attribute(&res, "skipped_option_ids"),
+        Some(option_ids[2].to_string())
+    );
+    assert_eq!(
+        res.attributes
+            .iter()
+            .filter(|attr| attr.key == "keeper_bounty")
+            .count(),
+        2
+    );
+    for option_id in &option_ids[..2] {
+        assert!(
+            CallInstrument::load(deps.as_ref().storage, option_id)
+                .unwrap()
+                .settled
+        );
+        assert_eq!(
+            OPTION_CLAIMS
+                .load(deps.as_ref().storage, option_id)
+                .unwrap(),
+            Uint128::new(99_000)
+        );
+    }
+
+    // settled and unknown options are skipped
+    let res = execute(
+        &mut deps,
+        "keeper",
+        &[],
+        crate::ExecuteMsg::SettleBatch {
+            option_ids: vec![option_ids[0], option_ids[1], 999],
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        attribute(&res, "skipped_option_ids"),
+        Some(format!("{},{},999", option_ids[0], option_ids[1]))
+    );
+
+    // options expiring without bids have no spread, the bounty for burning them is funded by the writer
+    let res = execute(
+        &mut deps,
+        "keeper",
+        &coins(300, "ATOM"),
+        crate::ExecuteMsg::FundBurnBounty {
+            option_id: option_ids[2],
+        },
+    );
+    assert_eq!(res, Err(ContractError::Unauthorized {}));
+    for option_id in &option_ids[2..] {
+        execute(
+            &mut deps,
+            WRITER,
+            &coins(300, "ATOM"),
+            crate::ExecuteMsg::FundBurnBounty {
+                option_id: *option_id,
+            },
+        )
+        .unwrap();
+    }
+    let res = execute(
+        &mut deps,
+        WRITER,
+        &coins(200, "ATOM"),
+        crate::ExecuteMsg::FundBurnBounty {
+            option_id: option_ids[2],
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "burn_bounty"), Some("500".to_string()));
+
+    let res = execute(
+        &mut deps,
+        "keeper",
+        &[],
+        crate::ExecuteMsg::BurnExpiredOption {
+            option_id: option_ids[2],
+            withdraw: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "keeper".to_string(),
+            amount: coins(500, "ATOM"),
+        })]
+    );
+    assert!(!BURN_BOUNTIES.has(deps.as_ref().storage, &option_ids[2]));
+    assert!(!CallInstrumentContract::default()
+        .tokens
+        .has(deps.as_ref().storage, &option_ids[2].to_string()));
+
+    // a bounty taking the whole spread leaves nothing to claim, the option is burned right away
+    let mut call = CallInstrument::load(deps.as_ref().storage, &option_ids[3]).unwrap();
+    call.bid = Uint128::new(500_600);
+    call.bidder = Some(Addr::unchecked("alice"));
+    call.save(deps.as_mut().storage, &option_ids[3]).unwrap();
+    let res = execute(
+        &mut deps,
+        "keeper",
+        &[],
+        crate::ExecuteMsg::SettleOption {
+            option_id: option_ids[3],
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "holder_amount"), Some("0".to_string()));
+    assert_eq!(attribute(&res, "claimable"), Some("false".to_string()));
+    assert!(res.messages.contains(&SubMsg::new(BankMsg::Send {
+        to_address: "keeper".to_string(),
+        amount: coins(600, "ATOM"),
+    })));
+    assert!(!OPTION_CLAIMS.has(deps.as_ref().storage, &option_ids[3]));
+    assert!(!CallInstrumentContract::default()
+        .tokens
+        .has(deps.as_ref().storage, &option_ids[3].to_string()));
+
+    // the burn bounty of an option ending any other way is returned to the writer
+    assert!(!BURN_BOUNTIES.has(deps.as_ref().storage, &option_ids[3]));
+    assert_eq!(
+        PENDING_REFUNDS
+            .load(deps.as_ref().storage, &Addr::unchecked(WRITER))
+            .unwrap(),
+        vec![(Denom::from("ATOM"), Uint128::new(300))]
+    );
+}
+
+#[test]
+fn burning_an_expired_option_releases_the_asset() {
+    let (mut deps, _) = setup(USER, &[]);
+    let config = Config::load(deps.as_ref().storage).unwrap();
+
+    // mocks the entitlement of the instrument on the asset, `None` once the asset left the vault
+    let mock_entitlement = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                            is_active: Option<bool>| {
+        deps.querier.update_wasm(move |query| {
+            let WasmQuery::Smart { msg, .. } = query else {
+                unreachable!()
+            };
+            match (from_binary(msg), is_active) {
+                (Ok(vault::msg::QueryMsg::CurrentEntitlementOperator { .. }), None) => {
+                    SystemResult::Ok(ContractResult::Err("asset not found".to_string()))
+                }
+                (Ok(vault::msg::QueryMsg::CurrentEntitlementOperator { .. }), Some(is_active)) => {
+                    let response = vault::msg::CurrentEntitlementOperatorResponse {
+                        is_active,
+                        operator: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_binary(&response).unwrap()))
+                }
+                _ => SystemResult::Ok(ContractResult::Ok(to_binary(&false).unwrap())),
+            }
+        });
+    };
+    let mint = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, env: &Env| {
+        crate::utils::mint_call(
+            deps.as_mut(),
+            env,
+            WRITER,
+            "vault_addr",
+            &NFT_ID.to_string(),
+            Uint128::new(500_000),
+            Expiration::AtTime(env.block.time.plus_seconds(DAY)),
+            ExerciseStyle::European,
+            &config,
+        )
+    };
+    let burn = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                env: &Env,
+                sender: &str,
+                option_id: OptionId,
+                withdraw: Option<bool>| {
+        contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(sender, &[]),
+            cw721_base::ExecuteMsg::Extension {
+                msg: crate::ExecuteMsg::BurnExpiredOption {
+                    option_id,
+                    withdraw,
+                },
+            },
+        )
+    };
+    let withdrawn = |res: &Response| {
+        res.events
+            .iter()
+            .find(|event| event.ty == ExpiredOptionBurned::TYPE)
+            .and_then(|event| event.attributes.iter().find(|attr| attr.key == "withdrawn"))
+            .map(|attr| attr.value.clone())
+    };
+
+    let mut env = mock_env();
+    let option_id = mint(&mut deps, &env).unwrap();
+    env.block.time = env.block.time.plus_seconds(DAY + 1);
+
+    // only the writer can pull the asset out of the vault
+    let res = burn(&mut deps, &env, "keeper", option_id, Some(true));
+    assert_eq!(res, Err(ContractError::Unauthorized {}));
+
+    // the entitlement lapsed with the option, there is nothing left to release in the vault
+    mock_entitlement(&mut deps, Some(false));
+    let res = burn(&mut deps, &env, "keeper", option_id, None).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(withdrawn(&res), Some("false".to_string()));
+    assert!(
+        CallInstrument::load(deps.as_ref().storage, &option_id)
+            .unwrap()
+            .settled
+    );
+    let res = burn(&mut deps, &env, "keeper", option_id, None);
+    assert_eq!(res, Err(ContractError::OptionAlreadySettled(option_id)));
+
+    // a new option can be written on the asset, burning it doesn't fail once the asset left the vault
+    let option_id = mint(&mut deps, &env).unwrap();
+    env.block.time = env.block.time.plus_seconds(DAY + 1);
+    mock_entitlement(&mut deps, None);
+    let res = burn(&mut deps, &env, WRITER, option_id, Some(true)).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(withdrawn(&res), Some("false".to_string()));
+
+    // an entitlement the instrument still holds is cleared, and the writer can withdraw the asset with it
+    let option_id = mint(&mut deps, &env).unwrap();
+    env.block.time = env.block.time.plus_seconds(DAY + 1);
+    mock_entitlement(&mut deps, Some(true));
+    let res = burn(&mut deps, &env, WRITER, option_id, Some(true)).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(
+            vault::utils::clear_entitlement_and_distribute_wasm_msg("vault_addr", NFT_ID, WRITER)
+                .unwrap()
+        )]
+    );
+    assert_eq!(withdrawn(&res), Some("true".to_string()));
+}
@@ -1,20 +1,30 @@
 // HELPERS
 use cosmwasm_std::{
-    ensure, from_binary, to_binary, Addr, CosmosMsg, DepsMut, Empty, Env, MessageInfo,
-    QuerierWrapper, StdError, StdResult, Uint128, WasmMsg,
+    ensure, from_binary, to_binary, Addr, Binary, BlockInfo, CosmosMsg, DepsMut, Empty, Env, Event,
+    MessageInfo, QuerierWrapper, StdResult, Storage, Timestamp, Uint128, WasmMsg,
 };
 use cw721_base::state::TokenInfo;
 use cw_utils::Expiration;
+use sha2::{Digest, Sha256};
 
 use common::{
     errors::ContractError,
-    types::{AssetId, OptionId},
+    events::OptionMinted,
+    nft::{self, RoyaltiesInfoResponse},
+    types::{AssetId, ExerciseStyle, OptionId},
 };
-use vault::msg::QueryMsg as VaultQueryMsg;
+use protocol::{msg::QueryMsg as ProtocolQueryMsg, state::Config as ProtocolConfig};
+use vault::msg::{CurrentEntitlementOperatorResponse, QueryMsg as VaultQueryMsg};
 
 use crate::{
     contract::CallInstrumentContract,
-    state::{update_vault_asset_option, CallInstrument, Config, VAULT_ASSET_OPTION},
+    msg::{
+        AntiSnipe, AuctionMode, OptionLifecycle, OptionMetadata, OptionStatus, SettlementBreakdown,
+    },
+    state::{
+        update_vault_asset_option, CallInstrument, Config, OptionTerms, OPTION_TERMS,
+        VAULT_ASSET_OPTION,
+    },
     InstantiateMsg,
 };
 
@@ -27,29 +37,23 @@ pub(crate) fn mint_call(
     asset_id: &AssetId,
     strike: Uint128,
     expiration: Expiration,
+    exercise_style: ExerciseStyle,
     config: &Config,
 ) -> Result<OptionId, ContractError> {
     // TODO it's ok?
     let writer_addr = Addr::unchecked(writer);
     let vault_addr = Addr::unchecked(vault);
 
-    let mut block_info = env.block.clone();
-    block_info.time = env.block.time.plus_seconds(config.minimum_option_duration);
-
-    // the settlement auction always occurs one day before expiration
-    ensure!(
-        !expiration.is_expired(&block_info),
-        StdError::generic_err("mint_option_with_vault - expires sooner than min duration",)
-    );
+    // a time based expiration keeps the settlement auction and the entitlement in step
+    config.check_expiration(&expiration, &env.block)?;
 
     // verify that, if there is a previous option on this asset, it has already settled.
     let prev_option_id = VAULT_ASSET_OPTION.may_load(deps.storage, (&vault_addr, asset_id))?;
     if let Some(prev_option_id) = prev_option_id {
         let prev_option = CallInstrument::load(deps.storage, &prev_option_id)?;
-        // let prev_option = CALL_INSTRUMENTS.load(deps.storage, &prev_option_id)?;
         ensure!(
             prev_option.settled,
-            StdError::generic_err("mint_call - previous option must be settled",)
+            ContractError::PreviousOptionNotSettled {}
         );
     }
 
@@ -63,9 +67,12 @@ pub(crate) fn mint_call(
         bid: Uint128::zero(),
         bidder: None,
         settled: false,
+        exercise_style,
+        auction_end: expiration,
     };
     let next_option_id = CallInstrument::inc(deps.storage)?;
     call_option.save(deps.storage, &next_option_id)?;
+    OPTION_TERMS.save(deps.storage, &next_option_id, &OptionTerms::from(config))?;
 
     update_vault_asset_option(deps.storage, &vault_addr, asset_id, next_option_id)?;
 
@@ -90,7 +97,7 @@ pub(crate) fn mint_call(
         owner: writer_addr,
         approvals: vec![],
         token_uri: None,
-        extension: Empty {},
+        extension: OptionMetadata::new(&call_option, config),
     };
     nft_contract
         .tokens
@@ -104,6 +111,26 @@ pub(crate) fn mint_call(
     Ok(next_option_id)
 }
 
+/// Builds the event of an option written by `mint_call`.
+pub(crate) fn option_minted_event(
+    storage: &dyn Storage,
+    option_id: &OptionId,
+    config: &Config,
+) -> StdResult<Event> {
+    let call = CallInstrument::load(storage, option_id)?;
+    Ok(OptionMinted {
+        option_id: *option_id,
+        writer: call.writer_addr,
+        vault: call.vault_addr,
+        asset_id: call.asset_id,
+        strike: call.strike,
+        denom: config.allowed_denom.clone(),
+        expiration: call.expiration,
+        exercise_style: call.exercise_style,
+    }
+    .into())
+}
+
 #[allow(dead_code)]
 pub(crate) fn is_beneficial_owner(
     querier: &QuerierWrapper,
@@ -164,6 +191,85 @@ pub(crate) fn is_beneficial_owner_or_operator(
     Ok((false, beneficial_owner))
 }
 
+/// Checks whether the instrument still holds an active entitlement on an asset of a vault.
+/// An asset that was already withdrawn from the vault has no entitlement.
+pub(crate) fn is_entitled_operator(
+    querier: &QuerierWrapper,
+    env: &Env,
+    vault_addr: &Addr,
+    asset_id: &AssetId,
+) -> bool {
+    querier
+        .query_wasm_smart::<CurrentEntitlementOperatorResponse>(
+            vault_addr,
+            &VaultQueryMsg::CurrentEntitlementOperator {
+                asset_id: asset_id.clone(),
+            },
+        )
+        .is_ok_and(|entitlement| {
+            entitlement.is_active && entitlement.operator == Some(env.contract.address.clone())
+        })
+}
+
+/// Returns the moment when the settlement auction of an option begins.
+/// The offset is expressed in seconds, so for a height based expiration the auction is open right away.
+pub(crate) fn auction_start(expiration: &Expiration, offset: u64) -> Expiration {
+    match expiration {
+        Expiration::AtTime(time) => Expiration::AtTime(Timestamp::from_nanos(
+            time.nanos()
+                .saturating_sub(offset.saturating_mul(1_000_000_000)),
+        )),
+        Expiration::AtHeight(_) => Expiration::AtHeight(0),
+        Expiration::Never {} => Expiration::Never {},
+    }
+}
+
+/// Gets the extended end of the settlement auction if a bid placed at the given block falls within
+/// the anti-snipe window. The extension is capped at `max_extension` past the expiration.
+pub(crate) fn extended_auction_end(
+    call: &CallInstrument,
+    anti_snipe: &AntiSnipe,
+    block: &BlockInfo,
+) -> Option<Expiration> {
+    let (Expiration::AtTime(auction_end), Expiration::AtTime(expiration)) =
+        (call.auction_end, call.expiration)
+    else {
+        return None;
+    };
+    if block.time.plus_seconds(anti_snipe.window) < auction_end {
+        return None;
+    }
+
+    let extended = auction_end
+        .plus_seconds(anti_snipe.extension)
+        .min(expiration.plus_seconds(anti_snipe.max_extension));
+    (extended > auction_end).then_some(Expiration::AtTime(extended))
+}
+
+/// The end of the reveal period of a sealed bid auction, which starts at the expiration of the option.
+pub(crate) fn reveal_end(expiration: &Expiration, reveal_period: u64) -> Expiration {
+    match expiration {
+        Expiration::AtTime(time) => Expiration::AtTime(time.plus_seconds(reveal_period)),
+        Expiration::AtHeight(height) => Expiration::AtHeight(*height),
+        Expiration::Never {} => Expiration::Never {},
+    }
+}
+
+/// The expiration of the vault entitlement of an option. In sealed bid auctions the entitlement
+/// outlives the option by the reveal period, so the asset stays in the vault until the settlement.
+pub(crate) fn entitlement_expiration(expiration: &Expiration, config: &Config) -> Expiration {
+    match config.auction_mode {
+        AuctionMode::Open => *expiration,
+        AuctionMode::SealedBid { reveal_period, .. } => reveal_end(expiration, reveal_period),
+    }
+}
+
+/// Builds the commitment of a sealed bid: the sha256 hash of `{bidder}:{option_id}:{amount}:{salt}`.
+pub fn bid_commitment(bidder: &Addr, option_id: &OptionId, amount: Uint128, salt: &str) -> Binary {
+    let preimage = format!("{}:{}:{}:{}", bidder, option_id, amount, salt);
+    Binary::from(Sha256::digest(preimage.as_bytes()).as_slice())
+}
+
 pub(crate) fn option_owner(deps: &DepsMut, env: &Env, token_id: String) -> StdResult<String> {
     let resp: cw721::OwnerOfResponse = from_binary(&CallInstrumentContract::default().query(
         deps.as_ref(),
@@ -177,6 +283,22 @@ pub(crate) fn option_owner(deps: &DepsMut, env: &Env, token_id: String) -> StdRe
     Ok(resp.owner)
 }
 
+/// Keeps the metadata of the option nft in sync with the option, unless the nft was burned.
+pub(crate) fn sync_option_metadata(
+    storage: &mut dyn Storage,
+    option_id: &OptionId,
+    call: &CallInstrument,
+    config: &Config,
+) -> StdResult<()> {
+    let nft_contract = CallInstrumentContract::default();
+    let token_id = option_id.to_string();
+    if let Some(mut token) = nft_contract.tokens.may_load(storage, &token_id)? {
+        token.extension = OptionMetadata::new(call, config);
+        nft_contract.tokens.save(storage, &token_id, &token)?;
+    }
+    Ok(())
+}
+
 pub fn burn_option_nft(
     deps: DepsMut,
     env: Env,
@@ -194,34 +316,166 @@ pub fn burn_option_nft(
     Ok(())
 }
 
-#[allow(clippy::too_many_arguments)]
+/// Moves the option nft from its owner to the recipient, clearing its approvals.
+pub(crate) fn transfer_option_nft(
+    deps: DepsMut,
+    env: &Env,
+    owner: &Addr,
+    recipient: &Addr,
+    token_id: String,
+) -> Result<(), ContractError> {
+    CallInstrumentContract::default().execute(
+        deps,
+        env.clone(),
+        MessageInfo {
+            sender: owner.clone(),
+            funds: vec![],
+        },
+        cw721_base::ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id,
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Loads the main protocol settings, which contain the protocol fees.
+pub(crate) fn protocol_config(
+    querier: &QuerierWrapper,
+    config: &Config,
+) -> StdResult<ProtocolConfig> {
+    querier.query_wasm_smart(&config.protocol_addr, &ProtocolQueryMsg::Config {})
+}
+
+/// Splits the high bid of an option between the writer, the holder, the protocol and the royalty receiver.
+/// The protocol fee is charged on the spread, the strike fee on the strike paid to the writer.
+/// The royalty is paid out of the spread and capped at it, so it never cuts into the strike of the writer.
+/// The keeper bounty is paid out of what is left of the spread.
+pub(crate) fn settlement_breakdown(
+    call: &CallInstrument,
+    protocol_config: &ProtocolConfig,
+    royalty: Option<RoyaltiesInfoResponse>,
+    keeper_bounty: Uint128,
+) -> Result<SettlementBreakdown, ContractError> {
+    let high_bidder = call
+        .bidder
+        .as_ref()
+        .ok_or(ContractError::NoWinningBidder {})?;
+    let spread = call.bid.checked_sub(call.strike)?;
+
+    // if the option writer is the high bidder they don't receive the strike because they bid on the spread.
+    let strike = if high_bidder != call.writer_addr {
+        call.strike
+    } else {
+        Uint128::zero()
+    };
+
+    let (protocol_fee, strike_fee) = match protocol_config.fee_recipient {
+        Some(_) => (
+            spread.multiply_ratio(protocol_config.protocol_fee_bps, 10_000u128),
+            strike.multiply_ratio(protocol_config.strike_fee_bps, 10_000u128),
+        ),
+        None => (Uint128::zero(), Uint128::zero()),
+    };
+
+    let writer_amount = strike.checked_sub(strike_fee)?;
+    let mut holder_amount = spread.checked_sub(protocol_fee)?;
+
+    let (royalty_amount, royalty_recipient) = match royalty {
+        Some(royalty) => {
+            let royalty_amount = royalty.royalty_amount.min(holder_amount);
+            holder_amount -= royalty_amount;
+            (royalty_amount, Some(Addr::unchecked(royalty.address)))
+        }
+        None => (Uint128::zero(), None),
+    };
+
+    let keeper_bounty = keeper_bounty.min(holder_amount);
+    holder_amount -= keeper_bounty;
+
+    Ok(SettlementBreakdown {
+        bid: call.bid,
+        writer_amount,
+        holder_amount,
+        protocol_fee,
+        strike_fee,
+        fee_recipient: protocol_config.fee_recipient.clone(),
+        royalty_amount,
+        royalty_recipient,
+        keeper_bounty,
+    })
+}
+
+/// Queries the cw2981 royalty owed on the settlement of an option, if royalties are enabled.
+pub(crate) fn settlement_royalty(
+    querier: &QuerierWrapper,
+    call: &CallInstrument,
+    config: &Config,
+) -> Option<RoyaltiesInfoResponse> {
+    if !config.royalties_enabled || call.bidder.is_none() {
+        return None;
+    }
+    nft::royalty_info(
+        querier,
+        &config.allowed_underlying_nft,
+        &call.asset_id,
+        call.bid,
+    )
+}
+
+/// Gets the minimum amount of the next valid bid: the current high bid increased
+/// by the min bid increment, and no less than the strike.
+pub(crate) fn min_next_bid(
+    call: &CallInstrument,
+    min_bid_increment_bps: u64,
+) -> StdResult<Uint128> {
+    let bid_increment = call.bid.multiply_ratio(min_bid_increment_bps, 10_000u128);
+    Ok(call.bid.checked_add(bid_increment)?.max(call.strike))
+}
+
+/// Gets the lifecycle stage of an option at the given block.
+pub(crate) fn option_lifecycle(
+    call: &CallInstrument,
+    config: &Config,
+    block: &BlockInfo,
+) -> OptionLifecycle {
+    if call.settled {
+        OptionLifecycle::Settled
+    } else if call.expiration.is_expired(block) && call.bidder.is_none() {
+        OptionLifecycle::ExpiredWithoutBids
+    } else if call.auction_end.is_expired(block) {
+        OptionLifecycle::AwaitingSettlement
+    } else if auction_start(&call.expiration, config.settlement_auction_start_offset)
+        .is_expired(block)
+    {
+        OptionLifecycle::AuctionOpen
+    } else {
+        OptionLifecycle::Active
+    }
+}
+
+/// Gets the status of an option at the given block.
+pub(crate) fn option_status(call: &CallInstrument, block: &BlockInfo) -> OptionStatus {
+    if call.settled {
+        OptionStatus::Settled
+    } else if call.expiration.is_expired(block) {
+        OptionStatus::Expired
+    } else {
+        OptionStatus::Active
+    }
+}
+
+/// The protocol is set as admin, it migrates deployed instruments with `MigrateContract`.
 pub fn call_instrument_instantiate_wasm_msg(
-    name: String,
-    symbol: String,
     code_id: u64,
-    nft_addr: String,
-    protocol_addr: String,
-    vault_factory_addr: String,
-    minimum_option_duration: u64,
-    allowed_denom: String,
-    min_bid_increment_bps: u64,
+    msg: InstantiateMsg,
     label: String,
 ) -> StdResult<CosmosMsg<Empty>> {
-    let msg = to_binary(&InstantiateMsg {
-        name,
-        symbol,
-        protocol_addr,
-        allowed_underlying_nft: nft_addr,
-        vault_factory_addr,
-        minimum_option_duration,
-        allowed_denom,
-        min_bid_increment_bps,
-    })?;
-
     Ok(CosmosMsg::Wasm(WasmMsg::Instantiate {
-        admin: None, // TODO set admin
+        admin: Some(msg.protocol_addr.clone()),
         code_id,
-        msg,
+        msg: to_binary(&msg)?,
         funds: vec![],
         label,
     }))
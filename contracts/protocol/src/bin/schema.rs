@@ -1,11 +1,12 @@
 use cosmwasm_schema::write_api;
 
-use protocol::*;
+use protocol::{msg::MigrateMsg, *};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
-        query: QueryMsg
+        query: QueryMsg,
+        migrate: MigrateMsg
     }
 }
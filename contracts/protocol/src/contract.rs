@@ -2,11 +2,12 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
 
-use common::errors::ContractError;
+use common::{errors::ContractError, migrate::set_migrated_version};
+use semver::Version;
 
 use crate::{
-    execute,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    execute, migrations,
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     query,
     state::{Config, ADMINS},
 };
@@ -52,6 +53,8 @@ pub fn execute(
     match msg {
         ExecuteMsg::Pause { duration } => execute::pause(deps, env, info.sender, duration),
 
+        ExecuteMsg::Unpause {} => execute::unpause(deps, env, info.sender),
+
         ExecuteMsg::SetVaultFactory { contract_addr } => {
             execute::set_vault_factory(deps, &info.sender, contract_addr)
         }
@@ -59,12 +62,48 @@ pub fn execute(
         ExecuteMsg::SetCallFactory { contract_addr } => {
             execute::set_call_factory(deps, &info.sender, contract_addr)
         }
+
+        ExecuteMsg::SetProtocolFee {
+            protocol_fee_bps,
+            strike_fee_bps,
+            fee_recipient,
+        } => execute::set_protocol_fee(
+            deps,
+            &info.sender,
+            protocol_fee_bps,
+            strike_fee_bps,
+            fee_recipient,
+        ),
+
+        ExecuteMsg::MigrateContract {
+            contract_addr,
+            new_code_id,
+            msg,
+        } => execute::migrate_contract(deps, &info.sender, contract_addr, new_code_id, msg),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => query::config(deps),
+
+        QueryMsg::IsPaused {} => query::is_paused(deps, &env),
+
+        QueryMsg::Ownership {} => query::ownership(deps),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = set_migrated_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if previous < Version::new(0, 2, 0) {
+        migrations::migrate_from_v0_1(deps.storage)?;
     }
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", previous.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION))
 }
@@ -1,9 +1,9 @@
-use cosmwasm_std::{Addr, DepsMut, Env, Response};
+use cosmwasm_std::{ensure, Addr, Binary, DepsMut, Env, Response, WasmMsg};
 use cw_utils::Duration;
 
 use common::errors::ContractError;
 
-use crate::state::{Config, PAUSED, PAUSERS};
+use crate::state::{Config, PAUSED, PAUSERS, UPGRADERS};
 
 pub fn set_vault_factory(
     deps: DepsMut,
@@ -41,6 +41,66 @@ pub fn set_call_factory(
         .add_attribute("sender", sender))
 }
 
+pub fn set_protocol_fee(
+    deps: DepsMut,
+    sender: &Addr,
+    protocol_fee_bps: u64,
+    strike_fee_bps: u64,
+    fee_recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.as_ref().storage, sender)?;
+
+    ensure!(
+        protocol_fee_bps <= 10_000 && strike_fee_bps <= 10_000,
+        ContractError::InvalidConfig {}
+    );
+    let fee_recipient = fee_recipient
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    let mut config = Config::load(deps.storage)?;
+    config.protocol_fee_bps = protocol_fee_bps;
+    config.strike_fee_bps = strike_fee_bps;
+    config.fee_recipient = fee_recipient.clone();
+    config.save(deps.storage)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "set_protocol_fee")
+        .add_attribute("sender", sender)
+        .add_attribute("protocol_fee_bps", protocol_fee_bps.to_string())
+        .add_attribute("strike_fee_bps", strike_fee_bps.to_string())
+        .add_attribute(
+            "fee_recipient",
+            fee_recipient.map_or("none".to_string(), |addr| addr.to_string()),
+        ))
+}
+
+pub fn migrate_contract(
+    deps: DepsMut,
+    sender: &Addr,
+    contract_addr: String,
+    new_code_id: u64,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    // only the owner or upgraders may call this method.
+    if cw_ownable::assert_owner(deps.storage, sender).is_err() {
+        UPGRADERS.check(deps.storage, sender)?;
+    }
+
+    let contract_addr = deps.api.addr_validate(&contract_addr)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Migrate {
+            contract_addr: contract_addr.to_string(),
+            new_code_id,
+            msg,
+        })
+        .add_attribute("action", "migrate_contract")
+        .add_attribute("sender", sender)
+        .add_attribute("contract_addr", contract_addr)
+        .add_attribute("new_code_id", new_code_id.to_string()))
+}
+
 pub fn pause(
     deps: DepsMut,
     env: Env,
@@ -61,3 +121,16 @@ pub fn pause(
         .add_attribute("sender", sender)
         .add_attribute("until", until.to_string()))
 }
+
+pub fn unpause(deps: DepsMut, env: Env, sender: Addr) -> Result<Response, ContractError> {
+    // only the core contract or pausers may call this method.
+    if sender != env.contract.address {
+        PAUSERS.check(deps.storage, &sender)?;
+    }
+
+    PAUSED.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "unpause")
+        .add_attribute("sender", sender))
+}
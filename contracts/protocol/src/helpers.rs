@@ -0,0 +1,33 @@
+use cosmwasm_std::{ensure, Addr, QuerierWrapper};
+use cw_ownable::{Ownership, OwnershipError};
+
+use common::errors::ContractError;
+
+use crate::msg::QueryMsg;
+
+/// Invoke `assert_not_paused` to check that the protocol is not paused.
+/// Contracts call it before any state-changing operation that is not an exit path.
+pub fn assert_not_paused(
+    querier: &QuerierWrapper,
+    protocol_addr: &Addr,
+) -> Result<(), ContractError> {
+    let is_paused: bool = querier.query_wasm_smart(protocol_addr, &QueryMsg::IsPaused {})?;
+    ensure!(!is_paused, ContractError::ProtocolPaused {});
+    Ok(())
+}
+
+/// Invoke `assert_protocol_owner` to check that the sender is the owner of the protocol.
+/// Contracts without an owner of their own use it to gate their settings.
+pub fn assert_protocol_owner(
+    querier: &QuerierWrapper,
+    protocol_addr: &Addr,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    let ownership: Ownership<Addr> =
+        querier.query_wasm_smart(protocol_addr, &QueryMsg::Ownership {})?;
+    ensure!(
+        ownership.owner.as_ref() == Some(sender),
+        OwnershipError::NotOwner
+    );
+    Ok(())
+}
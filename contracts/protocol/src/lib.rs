@@ -1,5 +1,7 @@
 pub mod contract;
 pub mod execute;
+pub mod helpers;
+pub mod migrations;
 pub mod msg;
 pub mod query;
 pub mod state;
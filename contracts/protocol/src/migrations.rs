@@ -0,0 +1,30 @@
+//! Storage migrations of the protocol, run by the `migrate` entry point.
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, StdResult, Storage};
+use cw_storage_plus::Item;
+
+use crate::state::Config;
+
+/// Storage layout of version 0.1, before protocol fees were added.
+mod v0_1 {
+    use super::*;
+
+    #[cw_serde]
+    pub struct Config {
+        pub vault_factory_addr: Option<Addr>,
+        pub call_factory_addr: Option<Addr>,
+    }
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+}
+
+/// Migrates the storage of version 0.1, no fees are charged until they are set by the owner.
+pub(crate) fn migrate_from_v0_1(storage: &mut dyn Storage) -> StdResult<()> {
+    let legacy = v0_1::CONFIG.load(storage)?;
+    Config {
+        vault_factory_addr: legacy.vault_factory_addr,
+        call_factory_addr: legacy.call_factory_addr,
+        ..Config::default()
+    }
+    .save(storage)
+}
@@ -1,4 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Binary;
 use cw_utils::Duration;
 
 #[allow(unused_imports)]
@@ -10,6 +11,9 @@ pub struct InstantiateMsg {
     pub owner: Option<String>,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 /// This structure describes the execute messages of the contract.
 #[cw_serde]
 pub enum ExecuteMsg {
@@ -17,6 +21,9 @@ pub enum ExecuteMsg {
     /// When paused the protocol contracts is unable to execute messages.
     Pause { duration: Duration },
 
+    /// Unpauses the protocol contracts before the pause duration has passed.
+    Unpause {},
+
     /// Allows an admin to set the address of the deployed vault factory.
     /// All protocol components, including the call factory, to look up the vault factory.
     SetVaultFactory { contract_addr: String },
@@ -24,6 +31,22 @@ pub enum ExecuteMsg {
     /// Allows an admin to set the address of the deployed covered call factory
     /// This address is used by other protocols searching for the registry of protocols.
     SetCallFactory { contract_addr: String },
+
+    /// Allows the owner to set the fees charged on settlement proceeds and their recipient.
+    /// Fees are expressed in basis points, no fees are charged without a recipient.
+    SetProtocolFee {
+        protocol_fee_bps: u64,
+        strike_fee_bps: u64,
+        fee_recipient: Option<String>,
+    },
+
+    /// Allows the owner or an upgrader to migrate a vault or an instrument to a new code id.
+    /// The factories instantiate them with the protocol as admin.
+    MigrateContract {
+        contract_addr: String,
+        new_code_id: u64,
+        msg: Binary,
+    },
 }
 
 #[cw_serde]
@@ -32,4 +55,12 @@ pub enum QueryMsg {
     /// Config returns contract settings.
     #[returns(Config)]
     Config {},
+
+    /// Returns true while the protocol is paused.
+    #[returns(bool)]
+    IsPaused {},
+
+    /// Returns the owner of the protocol, who governs the instruments.
+    #[returns(cw_ownable::Ownership<cosmwasm_std::Addr>)]
+    Ownership {},
 }
@@ -1,9 +1,21 @@
-use cosmwasm_std::{to_binary, Binary, Deps, StdResult};
+use cosmwasm_std::{to_binary, Binary, Deps, Env, StdResult};
 
-use crate::state::Config;
+use crate::state::{Config, PAUSED};
 
 pub fn config(deps: Deps) -> StdResult<Binary> {
     let config = Config::load(deps.storage)?;
 
     to_binary(&config)
 }
+
+pub fn is_paused(deps: Deps, env: &Env) -> StdResult<Binary> {
+    let is_paused = PAUSED
+        .may_load(deps.storage)?
+        .is_some_and(|until| !until.is_expired(&env.block));
+
+    to_binary(&is_paused)
+}
+
+pub fn ownership(deps: Deps) -> StdResult<Binary> {
+    to_binary(&cw_ownable::get_ownership(deps.storage)?)
+}
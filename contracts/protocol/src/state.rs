@@ -18,6 +18,13 @@ pub struct Config {
     pub vault_factory_addr: Option<Addr>,
     /// The address of the deployed covered call factory contract.
     pub call_factory_addr: Option<Addr>,
+    /// Fee charged on the spread paid to the option holder at settlement,
+    /// expressed in basis points (i.e. 1/100th of 1%)
+    pub protocol_fee_bps: u64,
+    /// Fee charged on the strike paid to the option writer at settlement, in basis points
+    pub strike_fee_bps: u64,
+    /// The address receiving protocol fees. No fees are charged if it is not set.
+    pub fee_recipient: Option<Addr>,
 }
 
 impl Config {
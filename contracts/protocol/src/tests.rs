@@ -1,11 +1,13 @@
 use std::sync::Mutex;
 
+use common::errors::ContractError;
 use cosmwasm_std::{
     from_binary,
     testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
-    Addr, MessageInfo, OwnedDeps, Response,
+    to_binary, Addr, MessageInfo, OwnedDeps, Response, Storage, SubMsg, WasmMsg,
 };
 use cw2::ContractVersion;
+use cw_utils::Duration;
 use once_cell::sync::Lazy;
 
 #[allow(unused_imports)]
@@ -116,4 +118,127 @@ fn not_owner_cant_set_vault_factory() {
     assert!(res.is_err()); // TODO add Err equals
 }
 
-// TODO test pause()
+#[test]
+fn set_protocol_fee() {
+    let (mut deps, info) = setup(OWNER);
+
+    let msg = ExecuteMsg::SetProtocolFee {
+        protocol_fee_bps: 250,
+        strike_fee_bps: 50,
+        fee_recipient: Some("fee_recipient_addr".to_string()),
+    };
+    let _ = contract::execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let res = contract::query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: Config = from_binary(&res).unwrap();
+    assert_eq!(config.protocol_fee_bps, 250);
+    assert_eq!(config.strike_fee_bps, 50);
+    assert_eq!(
+        config.fee_recipient,
+        Some(Addr::unchecked("fee_recipient_addr"))
+    );
+
+    let msg = ExecuteMsg::SetProtocolFee {
+        protocol_fee_bps: 10_001,
+        strike_fee_bps: 0,
+        fee_recipient: None,
+    };
+    let res = contract::execute(deps.as_mut(), mock_env(), info, msg);
+    assert_eq!(res, Err(ContractError::InvalidConfig {}));
+
+    let msg = ExecuteMsg::SetProtocolFee {
+        protocol_fee_bps: 0,
+        strike_fee_bps: 0,
+        fee_recipient: None,
+    };
+    let res = contract::execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), msg);
+    assert!(res.is_err());
+}
+
+#[test]
+fn pause_and_unpause() {
+    let (mut deps, _) = setup(OWNER);
+    let is_paused = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| -> bool {
+        from_binary(&contract::query(deps.as_ref(), mock_env(), QueryMsg::IsPaused {}).unwrap())
+            .unwrap()
+    };
+    // the core contract itself is allowed to pause
+    let info = mock_info(mock_env().contract.address.as_str(), &[]);
+
+    assert!(!is_paused(&deps));
+
+    let msg = ExecuteMsg::Pause {
+        duration: Duration::Time(3600),
+    };
+    let _ = contract::execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert!(is_paused(&deps));
+
+    let _ = contract::execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Unpause {}).unwrap();
+    assert!(!is_paused(&deps));
+}
+
+#[test]
+fn migrate_contract() {
+    let (mut deps, info) = setup(OWNER);
+
+    let msg = ExecuteMsg::MigrateContract {
+        contract_addr: "call_instrument_addr".to_string(),
+        new_code_id: 7,
+        msg: to_binary(&MigrateMsg {}).unwrap(),
+    };
+    let res = contract::execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Migrate {
+            contract_addr: "call_instrument_addr".to_string(),
+            new_code_id: 7,
+            msg: to_binary(&MigrateMsg {}).unwrap(),
+        })]
+    );
+
+    // only the owner and upgraders can migrate contracts
+    let res = contract::execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), msg.clone());
+    assert!(res.is_err());
+
+    UPGRADERS
+        .grant(deps.as_mut().storage, Addr::unchecked(USER))
+        .unwrap();
+    let res = contract::execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), msg);
+    assert!(res.is_ok());
+}
+
+#[test]
+fn migrate_from_v0_1() {
+    let (mut deps, _) = setup(OWNER);
+    cw2::set_contract_version(
+        deps.as_mut().storage,
+        "crates.io:interchainnft-options-protocol",
+        "0.1.0",
+    )
+    .unwrap();
+    deps.storage.set(
+        b"config",
+        br#"{"vault_factory_addr":"vault_factory_addr","call_factory_addr":null}"#,
+    );
+
+    let _ = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let config = Config::load(&deps.storage).unwrap();
+    assert_eq!(
+        config.vault_factory_addr,
+        Some(Addr::unchecked("vault_factory_addr"))
+    );
+    assert_eq!(config.protocol_fee_bps, 0);
+    assert_eq!(config.fee_recipient, None);
+
+    // the stored contract must be the protocol
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:other", "0.1.0").unwrap();
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {});
+    assert_eq!(
+        res,
+        Err(ContractError::InvalidMigration {
+            contract: "crates.io:other".to_string(),
+            version: "0.1.0".to_string(),
+        })
+    );
+}
@@ -0,0 +1,138 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult};
+
+use common::{errors::ContractError, migrate::set_migrated_version};
+use protocol::helpers::assert_not_paused;
+
+use crate::{
+    execute,
+    msg::{InstantiateMsg, MigrateMsg, PutInstrumentExecuteMsg, PutInstrumentQueryMsg},
+    query,
+    state::Config,
+};
+
+pub type PutInstrumentContract<'a> =
+    cw721_base::Cw721Contract<'a, Empty, Empty, PutInstrumentExecuteMsg, PutInstrumentQueryMsg>;
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:interchainnft-options-put-instrument";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config: Config = msg.clone().into();
+    config.validate(deps.api)?;
+    config.save(deps.storage)?;
+
+    let minter = env.contract.address.to_string();
+    let cw721_base_instantiate_msg = cw721_base::InstantiateMsg {
+        name: msg.name,
+        symbol: msg.symbol,
+        minter: minter.clone(),
+    };
+
+    PutInstrumentContract::default().instantiate(deps, env, info, cw721_base_instantiate_msg)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("minter", minter))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::ExecuteMsg<Empty, PutInstrumentExecuteMsg>,
+) -> Result<Response, ContractError> {
+    let config = Config::load(deps.storage)?;
+
+    // new options are not accepted while the protocol is paused,
+    // exercise and reclaim stay available so users are never trapped
+    if matches!(
+        msg,
+        cw721_base::ExecuteMsg::Extension {
+            msg: PutInstrumentExecuteMsg::MintPut { .. } | PutInstrumentExecuteMsg::Receive(_)
+        }
+    ) {
+        assert_not_paused(&deps.querier, &config.protocol_addr)?;
+    }
+
+    match msg {
+        // handle custom put option messages
+        cw721_base::ExecuteMsg::Extension { msg } => match msg {
+            PutInstrumentExecuteMsg::MintPut {
+                asset_id,
+                strike,
+                expiration,
+            } => execute::mint_put(deps, &env, info, asset_id, strike, expiration, &config),
+
+            PutInstrumentExecuteMsg::Exercise {
+                option_id,
+                vault_addr,
+                asset_id,
+            } => execute::exercise(deps, &env, info, &option_id, vault_addr, asset_id, &config),
+
+            PutInstrumentExecuteMsg::ReclaimStrike { option_id } => {
+                execute::reclaim_strike(deps, &env, info, &option_id, &config)
+            }
+
+            PutInstrumentExecuteMsg::Receive(msg) => {
+                execute::receive_cw20(deps, &env, info, msg, &config)
+            }
+        },
+
+        // cant mint cw721 token
+        cw721_base::ExecuteMsg::Mint { .. } => {
+            Err(StdError::generic_err("direct minting is forbidden").into())
+        }
+
+        // handle standard cw721 messages
+        _ => PutInstrumentContract::default()
+            .execute(deps, env, info, msg)
+            .map_err(|e| e.into()),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(
+    deps: Deps,
+    env: Env,
+    msg: cw721_base::QueryMsg<PutInstrumentQueryMsg>,
+) -> StdResult<Binary> {
+    match msg {
+        cw721_base::QueryMsg::Extension { msg } => match msg {
+            PutInstrumentQueryMsg::GetAssetId { option_id } => {
+                query::get_asset_id(deps, &option_id)
+            }
+
+            PutInstrumentQueryMsg::GetStrikePrice { option_id } => {
+                query::get_strike_price(deps, &option_id)
+            }
+
+            PutInstrumentQueryMsg::GetExpiration { option_id } => {
+                query::get_expiration(deps, &option_id)
+            }
+
+            PutInstrumentQueryMsg::GetWriter { option_id } => query::get_writer(deps, &option_id),
+        },
+        _ => PutInstrumentContract::default().query(deps, env, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = set_migrated_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", previous.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
@@ -0,0 +1,251 @@
+use cosmwasm_std::{ensure, from_binary, Addr, DepsMut, Env, MessageInfo, Response, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw_utils::{nonpayable, Expiration};
+
+use common::{
+    denom::{find_allowed_coin, Denom},
+    errors::ContractError,
+    types::{AssetId, OptionId},
+};
+use vault::{
+    msg::{CurrentEntitlementOperatorResponse, QueryMsg as VaultQueryMsg},
+    utils::{clear_entitlement_and_distribute_wasm_msg, set_beneficial_owner_wasm_msg},
+};
+
+use crate::{
+    msg::ReceiveMsg,
+    state::{Config, PutInstrument},
+    utils::{burn_option_nft, is_protocol_vault, option_owner, write_put},
+};
+
+// MESSAGE HANDLERS
+
+/// Mints a new put option with a given strike price and expiration, locking the strike sent by the writer.
+pub(crate) fn mint_put(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    asset_id: Option<AssetId>,
+    strike: Uint128,
+    expiration: Expiration,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    // the writer locks exactly the strike
+    let deposit = find_allowed_coin(info.funds.clone(), &config.allowed_denom)
+        .ok_or(ContractError::DenomNotAllowed {})?;
+    ensure!(
+        deposit.amount == strike && info.funds.len() == 1,
+        ContractError::InvalidStrikeDeposit {}
+    );
+
+    lock_strike(
+        deps,
+        env,
+        &info.sender,
+        asset_id,
+        strike,
+        expiration,
+        config,
+    )
+}
+
+/// Handles cw20 tokens sent to the instrument. Only the allowed cw20 token is accepted,
+/// the sent amount is locked as the strike of the new put option.
+pub(crate) fn receive_cw20(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    // info.sender - cw20 token contract
+    // wrapper.sender - writer of the put option
+    ensure!(
+        config.allowed_denom == Denom::Cw20(info.sender),
+        ContractError::DenomNotAllowed {}
+    );
+
+    let writer = deps.api.addr_validate(&wrapper.sender)?;
+
+    match from_binary(&wrapper.msg)? {
+        ReceiveMsg::MintPut {
+            asset_id,
+            expiration,
+        } => lock_strike(
+            deps,
+            env,
+            &writer,
+            asset_id,
+            wrapper.amount,
+            expiration,
+            config,
+        ),
+    }
+}
+
+fn lock_strike(
+    deps: DepsMut,
+    env: &Env,
+    writer: &Addr,
+    asset_id: Option<AssetId>,
+    strike: Uint128,
+    expiration: Expiration,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    ensure!(!strike.is_zero(), ContractError::ZeroStrike {});
+
+    let new_option_id = write_put(
+        deps.storage,
+        env,
+        writer,
+        asset_id,
+        strike,
+        expiration,
+        config,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "mint_put")
+        .add_attribute("option_id", new_option_id.to_string()))
+}
+
+/// Allows the option holder to sell an NFT to the writer at the strike price.
+/// The NFT is deposited in a protocol vault with an entitlement to this contract, so the
+/// beneficial ownership is moved to the writer and the asset is distributed to them.
+pub(crate) fn exercise(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    vault: String,
+    asset_id: AssetId,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let mut put = PutInstrument::load(deps.storage, option_id)?;
+
+    ensure!(
+        !put.settled,
+        ContractError::OptionAlreadySettled(option_id.to_owned())
+    );
+    ensure!(
+        !put.expiration.is_expired(&env.block),
+        ContractError::OptionIsExpired {}
+    );
+    ensure!(
+        option_owner(deps.storage, option_id)?.is_some_and(|owner| owner == info.sender),
+        ContractError::OnlyOptionOwner(info.sender.to_string())
+    );
+    if let Some(option_asset_id) = &put.asset_id {
+        ensure!(
+            *option_asset_id == asset_id,
+            ContractError::AssetMismatch {}
+        );
+    }
+
+    // only vaults of the protocol are trusted to deliver the asset
+    let vault_addr = deps.api.addr_validate(&vault)?;
+    ensure!(
+        is_protocol_vault(&deps.querier, &vault_addr, &asset_id, config)?,
+        ContractError::VaultNotFromProtocol {}
+    );
+    ensure!(
+        deps.querier.query_wasm_smart::<bool>(
+            &vault_addr,
+            &VaultQueryMsg::HoldsAsset {
+                asset_id: asset_id.clone(),
+            },
+        )?,
+        ContractError::AssetNotInVault(asset_id.clone())
+    );
+
+    // the holder must be the beneficial owner and this contract the entitled operator of the asset
+    let beneficial_owner: Option<Addr> = deps.querier.query_wasm_smart(
+        &vault_addr,
+        &VaultQueryMsg::BeneficialOwner {
+            asset_id: asset_id.clone(),
+        },
+    )?;
+    ensure!(
+        beneficial_owner.is_some_and(|owner| owner == info.sender),
+        ContractError::OnlyBeneficialOwner {}
+    );
+    let CurrentEntitlementOperatorResponse {
+        is_active,
+        operator,
+    } = deps.querier.query_wasm_smart(
+        &vault_addr,
+        &VaultQueryMsg::CurrentEntitlementOperator {
+            asset_id: asset_id.clone(),
+        },
+    )?;
+    ensure!(
+        is_active && operator.is_some_and(|op| op == env.contract.address),
+        ContractError::InstrumentNotOperator {}
+    );
+
+    put.settled = true;
+    put.save(deps.storage, option_id)?;
+
+    burn_option_nft(deps.storage, option_id)?;
+
+    // the writer becomes the owner of the asset and receives it, the holder receives the strike
+    let msgs = vec![
+        set_beneficial_owner_wasm_msg(
+            vault_addr.as_str(),
+            asset_id.as_str(),
+            put.writer_addr.as_str(),
+        )?,
+        clear_entitlement_and_distribute_wasm_msg(
+            vault_addr.as_str(),
+            asset_id.as_str(),
+            put.writer_addr.as_str(),
+        )?,
+        config.allowed_denom.send(&info.sender, &put.strike)?,
+    ];
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "exercise")
+        .add_attribute("option_id", option_id.to_string())
+        .add_attribute("asset_id", asset_id))
+}
+
+/// Allows the writer to reclaim the locked strike. Before expiration the writer must hold the option nft.
+pub(crate) fn reclaim_strike(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    option_id: &OptionId,
+    config: &Config,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let mut put = PutInstrument::load(deps.storage, option_id)?;
+
+    ensure!(
+        put.writer_addr == info.sender,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        !put.settled,
+        ContractError::OptionAlreadySettled(option_id.to_owned())
+    );
+    if !put.expiration.is_expired(&env.block) {
+        ensure!(
+            option_owner(deps.storage, option_id)?.is_some_and(|owner| owner == put.writer_addr),
+            ContractError::WriterMustOwnOption {}
+        );
+    }
+
+    put.settled = true;
+    put.save(deps.storage, option_id)?;
+
+    burn_option_nft(deps.storage, option_id)?;
+
+    Ok(Response::new()
+        .add_message(config.allowed_denom.send(&put.writer_addr, &put.strike)?)
+        .add_attribute("action", "reclaim_strike")
+        .add_attribute("option_id", option_id.to_string()))
+}
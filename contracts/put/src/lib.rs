@@ -0,0 +1,13 @@
+pub mod contract;
+pub mod execute;
+pub mod msg;
+pub mod query;
+pub mod state;
+pub mod utils;
+
+pub use crate::msg::{
+    InstantiateMsg, PutInstrumentExecuteMsg as ExecuteMsg, PutInstrumentQueryMsg as QueryMsg,
+};
+
+#[cfg(test)]
+mod tests;
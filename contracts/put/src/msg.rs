@@ -0,0 +1,86 @@
+use common::{
+    denom::Denom,
+    types::{AssetId, OptionId},
+};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, CustomMsg, Uint128};
+use cw_utils::Expiration;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Name of put option nft
+    pub name: String,
+    /// Symbol of put option nft
+    pub symbol: String,
+    /// The main protocol contract address
+    pub protocol_addr: String,
+    /// The address of the token contract permitted to serve as underlying assets for this instrument.
+    pub allowed_underlying_nft: String,
+    /// The address of the vault factory.
+    pub vault_factory_addr: String,
+    /// Default min duration of an created option
+    pub minimum_option_duration: u64,
+    /// Native denom or cw20 token of the strike locked by writers
+    pub allowed_denom: Denom,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+pub enum PutInstrumentExecuteMsg {
+    /// Mints a new put option with a given strike price and expiration. The writer locks the strike
+    /// by attaching it to the message. If `asset_id` is set, only that NFT can be sold to the writer,
+    /// otherwise any NFT from the underlying collection can be delivered.
+    MintPut {
+        asset_id: Option<AssetId>,
+        strike: Uint128,
+        expiration: Expiration,
+    },
+
+    /// Allows the option holder to sell an NFT to the writer at the strike price. The NFT must be
+    /// deposited in a protocol vault with an active entitlement granted to this contract.
+    Exercise {
+        option_id: OptionId,
+        vault_addr: String,
+        asset_id: AssetId,
+    },
+
+    /// Allows the writer to reclaim the locked strike. This is possible when the writer holds
+    /// the option nft, or after the option has expired without being exercised.
+    ReclaimStrike { option_id: OptionId },
+
+    /// Handles cw20 tokens sent to the instrument when the allowed denom is a cw20 token.
+    Receive(cw20::Cw20ReceiveMsg),
+}
+
+impl CustomMsg for PutInstrumentExecuteMsg {}
+
+/// Messages embedded into a cw20 `Send` to the instrument
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Mints a new put option locking the sent tokens as the strike.
+    MintPut {
+        asset_id: Option<AssetId>,
+        expiration: Expiration,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum PutInstrumentQueryMsg {
+    /// Gets the asset id the option is written for, or None if any NFT from the collection can be delivered
+    #[returns(Option<AssetId>)]
+    GetAssetId { option_id: OptionId },
+
+    #[returns(Uint128)]
+    GetStrikePrice { option_id: OptionId },
+
+    #[returns(Expiration)]
+    GetExpiration { option_id: OptionId },
+
+    #[returns(Addr)]
+    GetWriter { option_id: OptionId },
+}
+
+impl CustomMsg for PutInstrumentQueryMsg {}
@@ -0,0 +1,28 @@
+use common::types::OptionId;
+use cosmwasm_std::{to_binary, Binary, Deps, StdResult};
+
+use crate::state::PutInstrument;
+
+pub fn get_asset_id(deps: Deps, option_id: &OptionId) -> StdResult<Binary> {
+    let put = PutInstrument::load(deps.storage, option_id)?;
+
+    to_binary(&put.asset_id)
+}
+
+pub fn get_strike_price(deps: Deps, option_id: &OptionId) -> StdResult<Binary> {
+    let put = PutInstrument::load(deps.storage, option_id)?;
+
+    to_binary(&put.strike)
+}
+
+pub fn get_expiration(deps: Deps, option_id: &OptionId) -> StdResult<Binary> {
+    let put = PutInstrument::load(deps.storage, option_id)?;
+
+    to_binary(&put.expiration)
+}
+
+pub fn get_writer(deps: Deps, option_id: &OptionId) -> StdResult<Binary> {
+    let put = PutInstrument::load(deps.storage, option_id)?;
+
+    to_binary(&put.writer_addr)
+}
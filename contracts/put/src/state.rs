@@ -0,0 +1,96 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, StdResult, Storage, Uint128, Uint64};
+use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
+
+use common::{
+    denom::Denom,
+    errors::ContractError,
+    types::{AssetId, OptionId},
+};
+use macros::ConfigStorage;
+
+use crate::msg::InstantiateMsg;
+
+/// Put option settings store
+const CONFIG: Item<Config> = Item::new("config");
+
+#[cw_serde]
+#[derive(ConfigStorage)]
+pub struct Config {
+    /// The main protocol contract address (which contains configurations)
+    pub protocol_addr: Addr,
+    /// The address of the token contract permitted to serve as underlying assets for this instrument.
+    pub allowed_underlying_nft: Addr,
+    /// The address of the vault factory.
+    pub vault_factory_addr: Addr,
+    /// Default min duration in seconds of an created option
+    pub minimum_option_duration: u64,
+    /// Native denom or cw20 token of the strike locked by writers
+    pub allowed_denom: Denom,
+}
+
+impl Config {
+    pub fn validate(&self, api: &dyn Api) -> Result<(), ContractError> {
+        api.addr_validate(self.protocol_addr.as_str())?;
+        api.addr_validate(self.allowed_underlying_nft.as_str())?;
+        api.addr_validate(self.vault_factory_addr.as_str())?;
+        self.allowed_denom.validate(api)?;
+        Ok(())
+    }
+}
+
+impl From<InstantiateMsg> for Config {
+    fn from(value: InstantiateMsg) -> Self {
+        Self {
+            protocol_addr: Addr::unchecked(value.protocol_addr),
+            allowed_underlying_nft: Addr::unchecked(value.allowed_underlying_nft),
+            vault_factory_addr: Addr::unchecked(value.vault_factory_addr),
+            minimum_option_duration: value.minimum_option_duration,
+            allowed_denom: value.allowed_denom,
+        }
+    }
+}
+
+/// Counter for options
+pub const PUT_INSTRUMENTS_COUNT: Item<u64> = Item::new("put_options_count");
+
+/// Storage of all existing options contracts.
+pub const PUT_INSTRUMENTS: Map<&OptionId, PutInstrument> = Map::new("put_options");
+
+/// The metadata for each cash secured put option stored within the protocol
+#[cw_serde]
+pub struct PutInstrument {
+    /// The asset id that can be sold to the writer, None means any asset of the collection
+    pub asset_id: Option<AssetId>,
+    /// The address of the writer that created the put option
+    pub writer_addr: Addr,
+    /// The expiration time of the put option
+    pub expiration: Expiration,
+    /// The strike price locked by the writer
+    pub strike: Uint128,
+    /// Flag that marks when the option was exercised or the strike was reclaimed.
+    pub settled: bool,
+}
+
+impl PutInstrument {
+    pub fn load(store: &dyn Storage, option_id: &OptionId) -> StdResult<PutInstrument> {
+        PUT_INSTRUMENTS.load(store, option_id)
+    }
+
+    pub fn save(&self, store: &mut dyn Storage, option_id: &OptionId) -> StdResult<()> {
+        PUT_INSTRUMENTS.save(store, option_id, self)
+    }
+
+    pub fn count(storage: &dyn Storage) -> StdResult<u64> {
+        Ok(PUT_INSTRUMENTS_COUNT.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn inc(storage: &mut dyn Storage) -> StdResult<OptionId> {
+        let count = Uint64::new(Self::count(storage)?)
+            .checked_add(Uint64::new(1))?
+            .u64();
+        PUT_INSTRUMENTS_COUNT.save(storage, &count)?;
+        Ok(count)
+    }
+}
@@ -0,0 +1,184 @@
+use common::{denom::Denom, errors::ContractError};
+use cosmwasm_std::{
+    coins, from_binary,
+    testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+    to_binary, Addr, Coin, ContractResult, Empty, MessageInfo, OwnedDeps, StdError, SystemResult,
+    Uint128,
+};
+use cw2::ContractVersion;
+use cw_utils::Expiration;
+
+use crate::{
+    contract::{self, *},
+    msg::*,
+    state::*,
+};
+
+const USER: &str = "user_addr";
+const WRITER: &str = "writer_addr";
+const NFT_ADDR: &str = "bayc_nft_addr";
+const NFT_ID: &str = "id001";
+
+/// Mocks the protocol contract, which answers whether the protocol is paused.
+fn mock_protocol(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, is_paused: bool) {
+    deps.querier
+        .update_wasm(move |_| SystemResult::Ok(ContractResult::Ok(to_binary(&is_paused).unwrap())));
+}
+
+#[allow(dead_code)]
+fn setup(
+    info_sender: &str,
+    funds: &[Coin],
+) -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, MessageInfo) {
+    let mut deps = mock_dependencies();
+    mock_protocol(&mut deps, false);
+    let info = mock_info(info_sender, funds);
+
+    let msg = InstantiateMsg {
+        name: "put-option-nft".to_string(),
+        symbol: "PUTOPT".to_string(),
+        protocol_addr: "protocol_addr".to_string(),
+        allowed_underlying_nft: NFT_ADDR.to_string(),
+        vault_factory_addr: "vault_factory_addr".to_string(),
+        minimum_option_duration: 1,
+        allowed_denom: Denom::from("ATOM"),
+    };
+    let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg);
+
+    assert!(res.is_ok());
+
+    (deps, info)
+}
+
+fn mint_put_msg(strike: u128) -> cw721_base::ExecuteMsg<Empty, crate::ExecuteMsg> {
+    cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::MintPut {
+            asset_id: Some(NFT_ID.to_string()),
+            strike: Uint128::new(strike),
+            expiration: Expiration::AtTime(mock_env().block.time.plus_days(5)),
+        },
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let (deps, _) = setup(USER, &[]);
+
+    let config = Config::load(&deps.storage).unwrap();
+    assert_eq!(config.protocol_addr, "protocol_addr");
+    assert_eq!(config.vault_factory_addr, "vault_factory_addr");
+    assert_eq!(config.allowed_underlying_nft, NFT_ADDR.to_string());
+    assert_eq!(config.minimum_option_duration, 1);
+    assert_eq!(config.allowed_denom, Denom::from("ATOM"));
+
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(
+        version,
+        ContractVersion {
+            contract: "crates.io:interchainnft-options-put-instrument".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    );
+}
+
+#[test]
+fn mint_put_locks_strike() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    let info = mock_info(WRITER, &coins(100, "ATOM"));
+    let res = contract::execute(deps.as_mut(), mock_env(), info, mint_put_msg(100)).unwrap();
+    assert_eq!(res.attributes[1].value, "1");
+
+    let put = PutInstrument::load(&deps.storage, &1).unwrap();
+    assert_eq!(put.writer_addr, Addr::unchecked(WRITER));
+    assert_eq!(put.asset_id, Some(NFT_ID.to_string()));
+    assert_eq!(put.strike, Uint128::new(100));
+    assert!(!put.settled);
+
+    let owner = from_binary::<cw721::OwnerOfResponse>(
+        &contract::query(
+            deps.as_ref(),
+            mock_env(),
+            cw721_base::QueryMsg::OwnerOf {
+                token_id: "1".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(owner.owner, WRITER);
+}
+
+#[test]
+fn cant_mint_put_without_strike() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    let info = mock_info(WRITER, &coins(99, "ATOM"));
+    let res = contract::execute(deps.as_mut(), mock_env(), info, mint_put_msg(100));
+
+    assert_eq!(res, Err(ContractError::InvalidStrikeDeposit {}));
+}
+
+#[test]
+fn only_writer_reclaims_strike() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    let info = mock_info(WRITER, &coins(100, "ATOM"));
+    contract::execute(deps.as_mut(), mock_env(), info, mint_put_msg(100)).unwrap();
+
+    let msg = cw721_base::ExecuteMsg::Extension {
+        msg: crate::ExecuteMsg::ReclaimStrike { option_id: 1 },
+    };
+    let res = contract::execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), msg.clone());
+    assert_eq!(res, Err(ContractError::Unauthorized {}));
+
+    contract::execute(deps.as_mut(), mock_env(), mock_info(WRITER, &[]), msg).unwrap();
+    assert!(PutInstrument::load(&deps.storage, &1).unwrap().settled);
+}
+
+#[test]
+fn cant_direct_minting() {
+    let (mut deps, info) = setup(USER, &[]);
+
+    let msg = cw721_base::ExecuteMsg::Mint {
+        token_id: NFT_ID.to_string(),
+        owner: USER.to_string(),
+        token_uri: None,
+        extension: Empty {},
+    };
+    let res = contract::execute(deps.as_mut(), mock_env(), info, msg);
+
+    assert_eq!(
+        res,
+        Err(ContractError::Std(StdError::GenericErr {
+            msg: "direct minting is forbidden".to_string()
+        }))
+    )
+}
+
+#[test]
+fn cant_mint_put_without_time_expiration() {
+    let (mut deps, _) = setup(USER, &[]);
+
+    let mint = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, expiration| {
+        contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(WRITER, &coins(100, "ATOM")),
+            cw721_base::ExecuteMsg::Extension {
+                msg: crate::ExecuteMsg::MintPut {
+                    asset_id: Some(NFT_ID.to_string()),
+                    strike: Uint128::new(100),
+                    expiration,
+                },
+            },
+        )
+    };
+
+    let res = mint(&mut deps, Expiration::Never {});
+    assert_eq!(res, Err(ContractError::ExpirationNotTime {}));
+
+    let res = mint(&mut deps, Expiration::AtTime(mock_env().block.time));
+    assert_eq!(res, Err(ContractError::OptionDurationTooShort { min: 1 }));
+}
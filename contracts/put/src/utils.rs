@@ -0,0 +1,121 @@
+// HELPERS
+use cosmwasm_std::{
+    ensure, to_binary, Addr, CosmosMsg, Empty, Env, QuerierWrapper, StdResult, Storage, Uint128,
+    WasmMsg,
+};
+use cw721_base::state::TokenInfo;
+use cw_utils::Expiration;
+
+use common::{
+    errors::ContractError,
+    types::{AssetId, OptionId},
+};
+use vault_factory::msg::QueryMsg as VaultFactoryQueryMsg;
+
+use crate::{
+    contract::PutInstrumentContract,
+    state::{Config, PutInstrument},
+    InstantiateMsg,
+};
+
+pub(crate) fn write_put(
+    storage: &mut dyn Storage,
+    env: &Env,
+    writer: &Addr,
+    asset_id: Option<AssetId>,
+    strike: Uint128,
+    expiration: Expiration,
+    config: &Config,
+) -> Result<OptionId, ContractError> {
+    // only time based expirations are accepted, as on the call instrument
+    let Expiration::AtTime(time) = expiration else {
+        return Err(ContractError::ExpirationNotTime {});
+    };
+    ensure!(
+        time > env.block.time.plus_seconds(config.minimum_option_duration),
+        ContractError::OptionDurationTooShort {
+            min: config.minimum_option_duration,
+        }
+    );
+
+    // save the new option metadata
+    let put_option = PutInstrument {
+        asset_id,
+        writer_addr: writer.clone(),
+        expiration,
+        strike,
+        settled: false,
+    };
+    let next_option_id = PutInstrument::inc(storage)?;
+    put_option.save(storage, &next_option_id)?;
+
+    // mint the option NFT to the writer
+    let nft_contract = PutInstrumentContract::default();
+    let token = TokenInfo {
+        owner: writer.clone(),
+        approvals: vec![],
+        token_uri: None,
+        extension: Empty {},
+    };
+    nft_contract
+        .tokens
+        .update(storage, &next_option_id.to_string(), |old| match old {
+            Some(_) => Err(cw721_base::ContractError::Claimed {}),
+            None => Ok(token),
+        })?;
+
+    nft_contract.increment_tokens(storage)?;
+
+    Ok(next_option_id)
+}
+
+/// Returns the current owner of the option NFT, or None if it was already burned.
+pub(crate) fn option_owner(storage: &dyn Storage, option_id: &OptionId) -> StdResult<Option<Addr>> {
+    Ok(PutInstrumentContract::default()
+        .tokens
+        .may_load(storage, &option_id.to_string())?
+        .map(|token| token.owner))
+}
+
+/// Removes the option NFT regardless of the current holder.
+pub(crate) fn burn_option_nft(storage: &mut dyn Storage, option_id: &OptionId) -> StdResult<()> {
+    let nft_contract = PutInstrumentContract::default();
+    let token_id = option_id.to_string();
+    if nft_contract.tokens.has(storage, &token_id) {
+        nft_contract.tokens.remove(storage, &token_id)?;
+        nft_contract.decrement_tokens(storage)?;
+    }
+    Ok(())
+}
+
+/// Checks that the vault was created by the protocol's vault factory for this asset.
+pub(crate) fn is_protocol_vault(
+    querier: &QuerierWrapper,
+    vault_addr: &Addr,
+    asset_id: &AssetId,
+    config: &Config,
+) -> StdResult<bool> {
+    let registered_vault: Option<Addr> = querier.query_wasm_smart(
+        &config.vault_factory_addr,
+        &VaultFactoryQueryMsg::GetMultiOrSoloVault {
+            nft_addr: config.allowed_underlying_nft.to_string(),
+            nft_id: Some(asset_id.clone()),
+        },
+    )?;
+
+    Ok(registered_vault.is_some_and(|addr| addr == vault_addr))
+}
+
+pub fn put_instrument_instantiate_wasm_msg(
+    code_id: u64,
+    msg: InstantiateMsg,
+    label: String,
+) -> StdResult<CosmosMsg<Empty>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Instantiate {
+        admin: Some(msg.protocol_addr.clone()),
+        code_id,
+        msg: to_binary(&msg)?,
+        funds: vec![],
+        label,
+    }))
+}
@@ -5,11 +5,12 @@ use cosmwasm_std::{
 };
 use cw_utils::parse_reply_instantiate_data;
 
-use common::errors::ContractError;
+use common::{errors::ContractError, events::VaultCreated, migrate::set_migrated_version};
+use protocol::helpers::assert_not_paused;
 
 use crate::{
     execute::{self, INSTANTIATE_MULTI_VAULT_ID, INSTANTIATE_SOLO_VAULT_ID},
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     query,
     state::{Config, MULTI_VAULTS, SOLO_VAULTS, TMP_VAULT},
 };
@@ -51,6 +52,8 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     let config = Config::load(deps.storage)?;
 
+    assert_not_paused(&deps.querier, &config.protocol_addr)?;
+
     match msg {
         ExecuteMsg::MakeMultiVault { nft_addr } => {
             execute::make_multi_vault(deps, &info.sender, nft_addr, &config)
@@ -93,7 +96,16 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
                 &tmp.nft_addr,
                 &Addr::unchecked(&res.contract_address),
             )?;
-            Ok(Response::new().add_attribute("vault_addr", res.contract_address.to_string()))
+            Ok(Response::new()
+                .add_event(
+                    VaultCreated {
+                        nft_addr: tmp.nft_addr,
+                        nft_id: None,
+                        vault_addr: Addr::unchecked(&res.contract_address),
+                    }
+                    .into(),
+                )
+                .add_attribute("vault_addr", res.contract_address.to_string()))
         }
 
         INSTANTIATE_SOLO_VAULT_ID => {
@@ -112,6 +124,14 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
                         &Addr::unchecked(&res.contract_address),
                     )?;
                     Ok(Response::new()
+                        .add_event(
+                            VaultCreated {
+                                nft_addr: tmp.nft_addr,
+                                nft_id: Some(nft_id),
+                                vault_addr: Addr::unchecked(&res.contract_address),
+                            }
+                            .into(),
+                        )
                         .add_attribute("vault_addr", res.contract_address.to_string()))
                 }
                 None => Err(StdError::generic_err("make_solo_vault - nft_id must be Some").into()),
@@ -121,3 +141,13 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
         _ => Err(ContractError::UnknownReplyID {}),
     }
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = set_migrated_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", previous.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
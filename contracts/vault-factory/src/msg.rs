@@ -13,6 +13,9 @@ pub struct InstantiateMsg {
     pub owner: Option<String>,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Deploy a multi-asset vault if one has not already been deployed.
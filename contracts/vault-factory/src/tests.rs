@@ -1,9 +1,10 @@
 use std::sync::Mutex;
 
-use common::errors::ContractError;
+use common::{errors::ContractError, events::VaultCreated};
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
-    to_binary, Addr, CosmosMsg, MessageInfo, OwnedDeps, ReplyOn, Response, SubMsg, WasmMsg,
+    to_binary, Addr, Binary, ContractResult, CosmosMsg, Event, MessageInfo, OwnedDeps, Reply,
+    ReplyOn, Response, SubMsg, SubMsgResponse, SubMsgResult, SystemResult, WasmMsg,
 };
 use cw2::ContractVersion;
 use cw_ownable::OwnershipError;
@@ -28,12 +29,19 @@ const OWNER: &str = "owner_addr";
 const USER: &str = "user_addr";
 const VAULT_CODE_ID: u64 = 11;
 
+/// Mocks the protocol contract, which answers whether the protocol is paused.
+fn mock_protocol(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, is_paused: bool) {
+    deps.querier
+        .update_wasm(move |_| SystemResult::Ok(ContractResult::Ok(to_binary(&is_paused).unwrap())));
+}
+
 #[allow(dead_code)]
 #[allow(let_underscore_lock)]
 fn setup(info_sender: &str) -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, MessageInfo) {
     let _ = SETUP_LOGGER.lock().unwrap();
 
     let mut deps = mock_dependencies();
+    mock_protocol(&mut deps, false);
     let info = mock_info(info_sender, &[]);
 
     let msg = InstantiateMsg {
@@ -104,7 +112,7 @@ fn make_multi_vault() {
             .add_submessage(SubMsg {
                 id: INSTANTIATE_MULTI_VAULT_ID,
                 msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
-                    admin: None,
+                    admin: Some("protocol_addr".to_string()),
                     code_id: VAULT_CODE_ID,
                     msg: to_binary(&vault::msg::InstantiateMsg {
                         nft_addr: "nft_addr".to_string(),
@@ -140,7 +148,7 @@ fn make_solo_vault() {
             .add_submessage(SubMsg {
                 id: INSTANTIATE_SOLO_VAULT_ID,
                 msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
-                    admin: None,
+                    admin: Some("protocol_addr".to_string()),
                     code_id: VAULT_CODE_ID,
                     msg: to_binary(&vault::msg::InstantiateMsg {
                         nft_addr: "nft_addr".to_string(),
@@ -174,5 +182,44 @@ fn not_owner_cant_make_vaults() {
     );
 }
 
+#[test]
+fn multi_vault_created_event_has_no_nft_id() {
+    let (mut deps, info) = setup(OWNER);
+
+    let msg = ExecuteMsg::MakeMultiVault {
+        nft_addr: "nft_addr".to_string(),
+    };
+    contract::execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // protobuf encoded MsgInstantiateContractResponse with the vault address
+    let vault_addr = "vault_addr";
+    let mut data = vec![0x0a, vault_addr.len() as u8];
+    data.extend_from_slice(vault_addr.as_bytes());
+    let reply = Reply {
+        id: INSTANTIATE_MULTI_VAULT_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: Some(Binary::from(data)),
+        }),
+    };
+    let resp = contract::reply(deps.as_mut(), mock_env(), reply).unwrap();
+
+    // the chain rejects empty attribute values, so the unset nft id is left out
+    assert_eq!(
+        resp.events,
+        vec![Event::new(VaultCreated::TYPE)
+            .add_attribute("nft_addr", "nft_addr")
+            .add_attribute("vault_addr", vault_addr)]
+    );
+    assert!(resp
+        .events
+        .iter()
+        .all(|event| event.attributes.iter().all(|attr| !attr.value.is_empty())));
+    assert_eq!(
+        MULTI_VAULTS.load(&deps.storage, &Addr::unchecked("nft_addr")),
+        Ok(Addr::unchecked(vault_addr))
+    );
+}
+
 // TODO integration test create vault if already exists
 // TODO integration test get created vault
@@ -2,11 +2,12 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
 
-use common::errors::ContractError;
+use common::{errors::ContractError, migrate::set_migrated_version};
+use protocol::helpers::assert_not_paused;
 
 use crate::{
     execute,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg, VaultInstantiateData},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, VaultInstantiateData},
     query::{self},
     state::{Config, CONFIG},
 };
@@ -48,6 +49,17 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     let config = Config::load(deps.storage)?;
 
+    // deposits and new entitlements are not accepted while the protocol is paused,
+    // withdrawals and clearing entitlements stay available so users are never trapped
+    if matches!(
+        msg,
+        ExecuteMsg::ReceiveNft(_)
+            | ExecuteMsg::ImposeEntitlement { .. }
+            | ExecuteMsg::GrantEntitlement { .. }
+    ) {
+        assert_not_paused(&deps.querier, &config.protocol_addr)?;
+    }
+
     match msg {
         ExecuteMsg::ReceiveNft(msg) => execute::receive_cw721(deps, &env, &info, msg),
 
@@ -123,3 +135,13 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
     }
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = set_migrated_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", previous.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
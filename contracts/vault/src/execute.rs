@@ -1,12 +1,17 @@
-use cosmwasm_std::{ensure, from_binary, Addr, DepsMut, Env, MessageInfo, Response, StdError};
+use cosmwasm_std::{ensure, from_binary, Addr, DepsMut, Env, MessageInfo, Response};
 use cw_utils::{maybe_addr, Expiration};
 
-use common::{errors::ContractError, nft, types::AssetId};
+use common::{
+    errors::ContractError,
+    events::{AssetDeposited, AssetWithdrawn, BeneficialOwnerSet, EntitlementCleared},
+    nft,
+    types::AssetId,
+};
 
 use crate::{
     msg::SetEntitlement,
     state::{update_or_create_entitlement, Config, Entitlement, APPROVALS, ASSETS},
-    utils::{has_active_entitlement, register_entitlement},
+    utils::{entitlement_set_event, has_active_entitlement, register_entitlement},
 };
 
 /// Add an entitlement claim to the asset held within the vaultv (replace existed entitlement with new operator)
@@ -26,25 +31,19 @@ pub fn impose_entitlement(
     let beneficial_owner = entitlement
         .beneficial_owner
         .clone()
-        .ok_or(StdError::generic_err(
-            "impose_entitlement - beneficial owner must be set to impose an entitlement",
-        ))?;
+        .ok_or(ContractError::BeneficialOwnerNotSet {})?;
 
     let operator = deps.api.addr_validate(&operator)?;
 
-    register_entitlement(
-        deps,
-        env,
-        &asset_id,
-        Some(&entitlement),
-        &Entitlement {
-            beneficial_owner: Some(beneficial_owner),
-            operator: Some(operator),
-            expiry: Some(expiry),
-        },
-    )?;
+    let new_entitlement = Entitlement {
+        beneficial_owner: Some(beneficial_owner),
+        operator: Some(operator),
+        expiry: Some(expiry),
+    };
+    register_entitlement(deps, env, &asset_id, Some(&entitlement), &new_entitlement)?;
 
     Ok(Response::default()
+        .add_event(entitlement_set_event(&asset_id, &new_entitlement))
         .add_attribute("action", "impose_entitlement")
         .add_attribute("sender", sender)
         .add_attribute("asset_id", asset_id))
@@ -83,19 +82,15 @@ pub fn grant_entitlement(
 
     // the beneficial owner of an asset is able to directly set any entitlement on their own asset
     // as long as it has not already been committed to someone else.
-    register_entitlement(
-        deps,
-        env,
-        &asset_id,
-        Some(&entitlement),
-        &Entitlement {
-            beneficial_owner,
-            operator,
-            expiry: Some(expiry),
-        },
-    )?;
+    let new_entitlement = Entitlement {
+        beneficial_owner,
+        operator,
+        expiry: Some(expiry),
+    };
+    register_entitlement(deps, env, &asset_id, Some(&entitlement), &new_entitlement)?;
 
     Ok(Response::default()
+        .add_event(entitlement_set_event(&asset_id, &new_entitlement))
         .add_attribute("action", "grant_entitlement")
         .add_attribute("sender", sender)
         .add_attribute("asset_id", asset_id))
@@ -134,6 +129,13 @@ pub fn withdrawal_asset(
 
     Ok(Response::default()
         .add_submessage(transfer_nft_msg)
+        .add_event(
+            AssetWithdrawn {
+                asset_id: asset_id.clone(),
+                receiver: beneficial_owner,
+            }
+            .into(),
+        )
         .add_attribute("action", "withdrawal_asset")
         .add_attribute("sender", sender)
         .add_attribute("asset_id", asset_id.to_string()))
@@ -155,17 +157,17 @@ pub fn set_beneficial_owner(
     // otherwise only the current owner can update the beneficial owner
     if has_active_entitlement(&entitlement, env) {
         ensure!(
-            entitlement.operator.is_some_and(|operator| operator == sender),
-            StdError::generic_err("set_beneficial_owner - only the contract with the active entitlement can update the beneficial owner")
+            entitlement
+                .operator
+                .is_some_and(|operator| operator == sender),
+            ContractError::OnlyEntitledOperator {}
         );
     } else {
         ensure!(
             entitlement
                 .beneficial_owner
                 .is_some_and(|beneficial_owner| beneficial_owner == sender),
-            StdError::generic_err(
-                "set_beneficial_owner - only the current owner can update the beneficial owner"
-            )
+            ContractError::OnlyBeneficialOwner {}
         );
     }
 
@@ -176,13 +178,20 @@ pub fn set_beneficial_owner(
         deps,
         &asset_id,
         &Entitlement {
-            beneficial_owner: new_beneficial_owner,
+            beneficial_owner: new_beneficial_owner.clone(),
             operator: None,
             expiry: None,
         },
     )?;
 
     Ok(Response::default()
+        .add_event(
+            BeneficialOwnerSet {
+                asset_id: asset_id.clone(),
+                beneficial_owner: new_beneficial_owner,
+            }
+            .into(),
+        )
         .add_attribute("action", "set_beneficial_owner")
         .add_attribute("sender", sender)
         .add_attribute("asset_id", asset_id.to_string()))
@@ -209,9 +218,7 @@ pub fn clear_entitlement(
         entitlement
             .operator
             .is_some_and(|operator| operator == sender),
-        StdError::generic_err(
-            "clear_entitlement - only the entitled address can clear the entitlement",
-        )
+        ContractError::OnlyEntitledOperator {}
     );
 
     entitlement.expiry = None;
@@ -219,6 +226,13 @@ pub fn clear_entitlement(
     ASSETS.save(deps.storage, &asset_id, &entitlement)?;
 
     Ok(Response::default()
+        .add_event(
+            EntitlementCleared {
+                asset_id,
+                operator: sender.clone(),
+            }
+            .into(),
+        )
         .add_attribute("action", "clear_entitlement")
         .add_attribute("sender", sender))
 }
@@ -245,18 +259,24 @@ pub fn clear_entitlement_and_distribute(
         entitlement
             .beneficial_owner
             .is_some_and(|beneficial_owner| beneficial_owner == receiver),
-        StdError::generic_err(
-            "clear_entitlement_and_distribute - only the beneficial owner can receive the asset",
-        )
+        ContractError::ReceiverNotBeneficialOwner {}
     );
 
     let transfer_nft_msg = nft::transfer_nft(&config.nft_addr, &asset_id, &receiver_addr)?;
 
     // TODO maybe delete entitlement from ASSETS
-    clear_entitlement(deps, env, sender, asset_id)?;
+    let cleared = clear_entitlement(deps, env, sender, asset_id.clone())?;
 
     Ok(Response::new()
         .add_submessage(transfer_nft_msg)
+        .add_events(cleared.events)
+        .add_event(
+            AssetWithdrawn {
+                asset_id,
+                receiver: receiver_addr,
+            }
+            .into(),
+        )
         .add_attribute("action", "clear_entitlement_and_distribute"))
 }
 
@@ -282,6 +302,7 @@ pub fn receive_cw721(
 
     let entitlement: Option<SetEntitlement> = from_binary(&wrapper.msg)?;
 
+    let mut events = vec![];
     match entitlement {
         None => {
             update_or_create_entitlement(
@@ -293,27 +314,38 @@ pub fn receive_cw721(
                     expiry: None,
                 },
             )?;
+            events.push(
+                AssetDeposited {
+                    asset_id: wrapper.token_id.clone(),
+                    beneficial_owner: sender.clone(),
+                }
+                .into(),
+            );
         }
         Some(entitlement) => {
             if let Some(approved_operator) = entitlement.approved_operator {
                 APPROVALS.save(deps.storage, &wrapper.token_id, &approved_operator)?;
             }
 
-            register_entitlement(
-                deps,
-                env,
-                &wrapper.token_id,
-                None,
-                &Entitlement {
-                    beneficial_owner: Some(entitlement.beneficial_owner),
-                    operator: Some(entitlement.entitled_operator),
-                    expiry: Some(entitlement.expiry),
-                },
-            )?;
+            let new_entitlement = Entitlement {
+                beneficial_owner: Some(entitlement.beneficial_owner.clone()),
+                operator: Some(entitlement.entitled_operator),
+                expiry: Some(entitlement.expiry),
+            };
+            register_entitlement(deps, env, &wrapper.token_id, None, &new_entitlement)?;
+            events.push(
+                AssetDeposited {
+                    asset_id: wrapper.token_id.clone(),
+                    beneficial_owner: entitlement.beneficial_owner,
+                }
+                .into(),
+            );
+            events.push(entitlement_set_event(&wrapper.token_id, &new_entitlement));
         }
     }
 
     Ok(Response::default()
+        .add_events(events)
         .add_attribute("action", "receive_cw721")
         .add_attribute("from", sender)
         .add_attribute("token_id", wrapper.token_id))
@@ -11,6 +11,9 @@ pub struct InstantiateMsg {
     pub protocol_addr: String,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Executed when the contract receives a cw721 token.
@@ -3,12 +3,16 @@ use std::sync::Mutex;
 use cosmwasm_std::{
     from_binary,
     testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
-    Addr, Attribute, MessageInfo, OwnedDeps,
+    to_binary, Addr, Attribute, Env, MessageInfo, OwnedDeps,
 };
 use cw2::ContractVersion;
+use cw721::Cw721ReceiveMsg;
+use cw_utils::Expiration;
 use once_cell::sync::Lazy;
 
-use crate::{contract::*, msg::*, state::*};
+use common::errors::ContractError;
+
+use crate::{contract::*, execute, msg::*, state::*};
 
 static SETUP_LOGGER: Lazy<Mutex<()>> = Lazy::new(|| {
     env_logger::init();
@@ -89,6 +93,119 @@ fn proper_multi_vault_initialization() {
     );
 }
 
+fn deposit(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    env: &Env,
+    token_id: &str,
+    entitlement: Option<SetEntitlement>,
+) -> Result<(), ContractError> {
+    execute::receive_cw721(
+        deps.as_mut(),
+        env,
+        &mock_info("nft_addr", &[]),
+        Cw721ReceiveMsg {
+            sender: "writer".to_string(),
+            token_id: token_id.to_string(),
+            msg: to_binary(&entitlement).unwrap(),
+        },
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn entitlement_errors_are_typed() {
+    let (mut deps, _) = setup_multi_vault(SENDER);
+    let env = mock_env();
+    let writer = Addr::unchecked("writer");
+    let operator = Addr::unchecked("call_addr");
+    let stranger = Addr::unchecked("stranger");
+    let entitlement = |expiry| SetEntitlement {
+        beneficial_owner: writer.clone(),
+        entitled_operator: operator.clone(),
+        approved_operator: None,
+        expiry,
+    };
+
+    // an entitlement must not be already expired
+    let err = deposit(
+        &mut deps,
+        &env,
+        "0",
+        Some(entitlement(Expiration::AtTime(env.block.time))),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidExpiry {});
+
+    let expiry = Expiration::AtTime(env.block.time.plus_seconds(1_000));
+    deposit(&mut deps, &env, "1", Some(entitlement(expiry))).unwrap();
+
+    let err = execute::impose_entitlement(
+        deps.as_mut(),
+        &env,
+        &operator,
+        "2".to_string(),
+        operator.to_string(),
+        expiry,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetNotFound("2".to_string()));
+
+    // only the operator moves an asset under an active entitlement
+    let err = execute::set_beneficial_owner(
+        deps.as_mut(),
+        &env,
+        &writer,
+        "1".to_string(),
+        "stranger".to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OnlyEntitledOperator {});
+
+    let err =
+        execute::clear_entitlement(deps.as_mut(), &env, &stranger, "1".to_string()).unwrap_err();
+    assert_eq!(err, ContractError::OnlyEntitledOperator {});
+
+    let err = execute::grant_entitlement(
+        deps.as_mut(),
+        &env,
+        &stranger,
+        "1".to_string(),
+        "stranger".to_string(),
+        "stranger".to_string(),
+        expiry,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OnlyBeneficialOwnerOrOperator {});
+
+    let config = Config::load(&deps.storage).unwrap();
+    let err = execute::clear_entitlement_and_distribute(
+        deps.as_mut(),
+        &env,
+        &operator,
+        "1".to_string(),
+        "stranger".to_string(),
+        &config,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ReceiverNotBeneficialOwner {});
+
+    execute::clear_entitlement(deps.as_mut(), &env, &operator, "1".to_string()).unwrap();
+    let err =
+        execute::clear_entitlement(deps.as_mut(), &env, &operator, "1".to_string()).unwrap_err();
+    assert_eq!(err, ContractError::NoActiveEntitlement {});
+
+    // without an entitlement only the beneficial owner moves the asset
+    let err = execute::set_beneficial_owner(
+        deps.as_mut(),
+        &env,
+        &operator,
+        "1".to_string(),
+        "stranger".to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OnlyBeneficialOwner {});
+}
+
 /*
 #[test]
 fn make_multi_vault() {
@@ -107,7 +224,7 @@ fn make_multi_vault() {
             .add_submessage(SubMsg {
                 id: INSTANTIATE_MULTI_VAULT_ID,
                 msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
-                    admin: None,
+                    admin: Some("cosmos_protocol".to_string()),
                     code_id: MULTI_VAULT_CODE_ID,
                     msg: to_binary(&vault::msg::InstantiateMsg {
                         nft_addr: "cosmos_nft_addr".to_string(),
@@ -144,7 +261,7 @@ fn make_solo_vault() {
             .add_submessage(SubMsg {
                 id: INSTANTIATE_SOLO_VAULT_ID,
                 msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
-                    admin: None,
+                    admin: Some("cosmos_protocol".to_string()),
                     code_id: MULTI_VAULT_CODE_ID,
                     msg: to_binary(&vault::msg::InstantiateMsg {
                         nft_addr: "cosmos_nft_addr".to_string(),
@@ -1,9 +1,9 @@
-use cosmwasm_std::{
-    ensure, to_binary, CosmosMsg, DepsMut, Empty, Env, StdError, StdResult, WasmMsg,
-};
+use cosmwasm_std::{ensure, to_binary, CosmosMsg, DepsMut, Empty, Env, Event, StdResult, WasmMsg};
+use cw_utils::Expiration;
 
 use common::{
     errors::ContractError,
+    events::EntitlementSet,
     types::{AssetId, TokenId},
 };
 
@@ -12,6 +12,7 @@ use crate::{
     state::{update_or_create_entitlement, Entitlement},
 };
 
+/// Instantiates a vault with the protocol as admin, so it can be migrated by the protocol.
 pub fn vault_instantiate_wasm_msg(
     code_id: u64,
     nft_addr: String,
@@ -22,11 +23,11 @@ pub fn vault_instantiate_wasm_msg(
     let msg = to_binary(&InstantiateMsg {
         nft_addr,
         nft_id,
-        protocol_addr,
+        protocol_addr: protocol_addr.clone(),
     })?;
 
     Ok(CosmosMsg::Wasm(WasmMsg::Instantiate {
-        admin: None, // TODO set admin
+        admin: Some(protocol_addr),
         code_id,
         msg,
         funds: vec![],
@@ -49,6 +50,23 @@ pub fn set_beneficial_owner_wasm_msg(
     }))
 }
 
+pub fn impose_entitlement_wasm_msg(
+    contract_addr: &str,
+    asset_id: &str,
+    operator: &str,
+    expiry: Expiration,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract_addr.into(),
+        msg: to_binary(&ExecuteMsg::ImposeEntitlement {
+            asset_id: asset_id.into(),
+            operator: operator.into(),
+            expiry,
+        })?,
+        funds: vec![],
+    }))
+}
+
 pub fn clear_entitlement_wasm_msg(contract_addr: &str, asset_id: &str) -> StdResult<CosmosMsg> {
     Ok(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: contract_addr.into(),
@@ -89,6 +107,17 @@ pub(crate) fn _asset_id_to_nft_id(asset_id: AssetId) -> TokenId {
     asset_id
 }
 
+/// Builds the event of an entitlement registered on an asset.
+pub(crate) fn entitlement_set_event(asset_id: &AssetId, entitlement: &Entitlement) -> Event {
+    EntitlementSet {
+        asset_id: asset_id.clone(),
+        beneficial_owner: entitlement.beneficial_owner.clone(),
+        operator: entitlement.operator.clone(),
+        expiry: entitlement.expiry,
+    }
+    .into()
+}
+
 pub(crate) fn register_entitlement(
     deps: DepsMut,
     env: &Env,
@@ -108,7 +137,7 @@ pub(crate) fn register_entitlement(
 
     ensure!(
         !new.expiry.unwrap().is_expired(&env.block),
-        StdError::generic_err("register_entitlement - entitlement must expire in the future",)
+        ContractError::InvalidExpiry {}
     );
 
     update_or_create_entitlement(deps, asset_id, new)?;
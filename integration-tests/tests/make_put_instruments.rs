@@ -0,0 +1,42 @@
+use cosmwasm_std::{coin, Uint128};
+use cw_utils::Expiration;
+use testing::{mock_contracts::PutInstrument, mock_env::MockEnvBuilder};
+
+#[test]
+fn make_put_instrument_and_mint_put_flow() {
+    // 1. deploy and instantiate call instrument factory and underlying nft
+    let mut mock_env = MockEnvBuilder::new(None, None, None, None).build();
+    let underlying_nft = mock_env.underlying_nft.clone();
+    let call_factory = mock_env.call_factory.clone();
+
+    // 2. make put instrument for nft
+    call_factory.make_put_instrument(&mut mock_env, underlying_nft.contract_addr.as_str());
+    let put_instrument = PutInstrument {
+        contract_addr: call_factory
+            .query_put_instrument(&mock_env, underlying_nft.contract_addr.as_str())
+            .unwrap(),
+    };
+
+    // 3. mint put option locking the strike
+    let writer = mock_env.writer.clone();
+    mock_env.fund_account(&writer, &[coin(100, "ATOM")]);
+    let expiration = Expiration::AtTime(mock_env.query_block_info().time.plus_days(5));
+    let option_id = put_instrument.mint_put(&mut mock_env, None, coin(100, "ATOM"), expiration);
+
+    // 4. strike is locked in the put instrument
+    assert_eq!(
+        put_instrument.query_get_strike_price(&mock_env, option_id),
+        Uint128::new(100)
+    );
+    assert_eq!(
+        put_instrument.query_get_writer(&mock_env, option_id),
+        writer
+    );
+    assert_eq!(
+        mock_env
+            .query_balance(&put_instrument.contract_addr, "ATOM")
+            .unwrap()
+            .amount,
+        Uint128::new(100)
+    );
+}
@@ -0,0 +1,97 @@
+use call::msg::MintBatchEntry;
+use cosmwasm_std::Uint128;
+use cw_utils::Expiration;
+use testing::{mock_contracts::CallInstrument, mock_env::MockEnvBuilder};
+
+#[test]
+fn mint_batch_with_nft_flow() {
+    // 1. deploy and instantiate protocol, vault/call factories and underlying nft contracts
+    let mut mock_env = MockEnvBuilder::new(None, None, None, None).build();
+    let underlying_nft = mock_env.underlying_nft.clone();
+    let call_factory = mock_env.call_factory.clone();
+    let vault_factory = mock_env.vault_factory.clone();
+
+    let token_ids = ["id001", "id002", "id003"];
+    let token_owner = mock_env.writer.clone();
+
+    // 2. make multi vault and call instrument for nft
+    let vault_addr =
+        vault_factory.make_multi_vault(&mut mock_env, underlying_nft.contract_addr.as_str());
+    call_factory.make_call_instrument(&mut mock_env, underlying_nft.contract_addr.as_str());
+    let call_instrument = CallInstrument {
+        contract_addr: call_factory
+            .query_call_instrument(&mock_env, underlying_nft.contract_addr.as_str())
+            .unwrap(),
+    };
+
+    // 3. mint nfts, only the first two are approved to the call instrument
+    for (i, token_id) in token_ids.iter().enumerate() {
+        underlying_nft.mint(&mut mock_env, token_id, token_owner.as_str());
+        if i < 2 {
+            underlying_nft.approve(
+                &mut mock_env,
+                token_owner.as_str(),
+                call_instrument.contract_addr.as_str(),
+                token_id,
+            );
+        }
+    }
+
+    let expiration = Expiration::AtTime(mock_env.query_block_info().time.plus_days(1));
+    let entries = |token_ids: &[&str]| {
+        token_ids
+            .iter()
+            .enumerate()
+            .map(|(i, token_id)| MintBatchEntry {
+                asset_id: token_id.to_string(),
+                strike: Uint128::new(5 + i as u128),
+                expiration,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // 4. the batch fails as a whole when one of the nfts is not approved
+    assert!(call_instrument
+        .mint_batch_with_nft(
+            &mut mock_env,
+            underlying_nft.contract_addr.as_str(),
+            entries(&token_ids),
+        )
+        .is_err());
+    for token_id in token_ids {
+        assert_eq!(underlying_nft.query_owner(&mock_env, token_id), token_owner);
+    }
+
+    // 5. mint call options for the approved nfts
+    let option_ids = call_instrument
+        .mint_batch_with_nft(
+            &mut mock_env,
+            underlying_nft.contract_addr.as_str(),
+            entries(&token_ids[..2]),
+        )
+        .unwrap();
+    assert_eq!(option_ids.len(), 2);
+
+    for (i, option_id) in option_ids.into_iter().enumerate() {
+        assert_eq!(
+            call_instrument.query_get_asset_id(&mock_env, option_id),
+            token_ids[i],
+        );
+        assert_eq!(
+            call_instrument.query_get_strike_price(&mock_env, option_id),
+            Uint128::new(5 + i as u128),
+        );
+        assert_eq!(
+            call_instrument.query_get_vault_address(&mock_env, option_id),
+            vault_addr,
+        );
+        assert_eq!(
+            underlying_nft.query_owner(&mock_env, token_ids[i]),
+            vault_addr
+        );
+    }
+    assert_eq!(
+        underlying_nft.query_owner(&mock_env, token_ids[2]),
+        token_owner
+    );
+}
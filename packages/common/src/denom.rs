@@ -1,48 +1,67 @@
-//! Convenience functions for generically handling cw20::Denom
+//! Convenience functions for generically handling native and cw20 denoms
 use std::fmt::Display;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{coin, coins, Addr, BankMsg, Coin, CosmosMsg};
+use cosmwasm_std::{
+    coins, to_binary, Addr, Api, BankMsg, Coin, CosmosMsg, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
 
 #[cw_serde]
 #[derive(Hash, Eq, PartialOrd, Ord)]
-pub struct Denom(String);
+pub enum Denom {
+    /// Native coin of the chain, e.g. `uatom`
+    Native(String),
+    /// Fungible token implemented by a cw20 contract
+    Cw20(Addr),
+}
 
 impl Denom {
-    pub fn coins<T: Clone + Into<u128>>(&self, amount: &T) -> Vec<Coin> {
-        coins(amount.clone().into(), self.0.clone())
-    }
-
-    pub fn coin<T: Clone + Into<u128>>(&self, amount: &T) -> Coin {
-        coin(amount.clone().into(), self.0.clone())
+    /// Builds a message transferring `amount` of the denom to the recipient.
+    pub fn send<T: Clone + Into<u128>, M>(
+        &self,
+        to: impl Into<String>,
+        amount: &T,
+    ) -> StdResult<CosmosMsg<M>> {
+        let amount: u128 = amount.clone().into();
+        match self {
+            Denom::Native(denom) => Ok(CosmosMsg::Bank(BankMsg::Send {
+                to_address: to.into(),
+                amount: coins(amount, denom),
+            })),
+            Denom::Cw20(contract_addr) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: to.into(),
+                    amount: Uint128::new(amount),
+                })?,
+                funds: vec![],
+            })),
+        }
     }
 
-    pub fn send<T: Clone + Into<u128>, M>(&self, to: &Addr, amount: &T) -> CosmosMsg<M> {
-        CosmosMsg::Bank(BankMsg::Send {
-            to_address: to.to_string(),
-            amount: self.coins(amount),
-        })
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        if let Denom::Cw20(contract_addr) = self {
+            api.addr_validate(contract_addr.as_str())?;
+        }
+        Ok(())
     }
 
-    // pub fn query_balance(&self, q: QuerierWrapper<KujiraQuery>, addr: &Addr) -> StdResult<Coin> {
-    //     q.query_balance(addr.clone(), self.0.to_string())
-    // }
-
     pub fn as_bytes(&self) -> &[u8] {
-        self.0.as_bytes()
+        self.as_ref().as_bytes()
     }
 
-    // pub fn from_cw20(value: cw20::Denom) -> Self {
-    //     match value {
-    //         cw20::Denom::Native(x) => Self::from(x),
-    //         cw20::Denom::Cw20(_) => panic!("CW20 Unsupported"),
-    //     }
-    // }
+    pub fn from_cw20(value: cw20::Denom) -> Self {
+        match value {
+            cw20::Denom::Native(x) => Self::Native(x),
+            cw20::Denom::Cw20(x) => Self::Cw20(x),
+        }
+    }
 }
 
 impl Display for Denom {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.fmt(f)
+        self.as_ref().fmt(f)
     }
 }
 
@@ -51,13 +70,16 @@ where
     T: Into<String>,
 {
     fn from(t: T) -> Self {
-        Self(t.into())
+        Self::Native(t.into())
     }
 }
 
 impl AsRef<str> for Denom {
     fn as_ref(&self) -> &str {
-        self.0.as_ref()
+        match self {
+            Denom::Native(denom) => denom.as_ref(),
+            Denom::Cw20(contract_addr) => contract_addr.as_str(),
+        }
     }
 }
 
@@ -67,6 +89,10 @@ pub fn has_allowed_denoms(coins: Vec<Coin>, denoms: Vec<Denom>) -> bool {
         .all(|c| denoms.contains(&Denom::from(c.denom)))
 }
 
-pub fn find_allowed_coin(coins: Vec<Coin>, denom: &str) -> Option<Coin> {
-    coins.into_iter().find(|c| c.denom == denom)
+/// Finds the attached coin of a native denom, cw20 tokens can't be attached as funds.
+pub fn find_allowed_coin(coins: Vec<Coin>, denom: &Denom) -> Option<Coin> {
+    match denom {
+        Denom::Native(denom) => coins.into_iter().find(|c| &c.denom == denom),
+        Denom::Cw20(_) => None,
+    }
 }
@@ -1,10 +1,12 @@
-use cosmwasm_std::{Addr, DivideByZeroError, OverflowError, StdError};
+use cosmwasm_std::{Addr, DivideByZeroError, OverflowError, StdError, Uint128};
 use cw_ownable::OwnershipError;
-use cw_utils::ParseReplyError;
+use cw_utils::{ParseReplyError, PaymentError};
 use thiserror::Error;
 
-use crate::types::AssetId;
+use crate::types::{AssetId, OptionId};
 
+/// Errors of the protocol contracts. Every message starts with a stable error code followed by a colon,
+/// clients should match on the code rather than on the rest of the message.
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error(transparent)]
@@ -28,48 +30,252 @@ pub enum ContractError {
     #[error(transparent)]
     DivideByZeroError(#[from] DivideByZeroError),
 
-    #[error("Unauthorized")]
+    #[error(transparent)]
+    Payment(#[from] PaymentError),
+
+    #[error("UNAUTHORIZED: Unauthorized")]
     Unauthorized {},
 
-    #[error("Configuration not found.")]
+    #[error("CONFIG_NOT_FOUND: Configuration not found.")]
     ConfigNotFound {},
 
-    #[error("Invalid config parameters")]
+    #[error("INVALID_CONFIG: Invalid config parameters")]
     InvalidConfig {},
 
-    #[error("Vault was already created")]
+    #[error("VAULT_WAS_CREATED: Vault was already created")]
     VaultWasCreated {},
 
-    #[error("Failed to parse or process reply message")]
+    #[error("FAILED_TO_PARSE_REPLY: Failed to parse or process reply message")]
     FailedToParseReply {},
 
-    #[error("An unknown reply ID was received")]
+    #[error("UNKNOWN_REPLY_ID: An unknown reply ID was received")]
     UnknownReplyID {},
 
-    #[error("Invalid nft. Got ({received}), expected ({expected})")]
+    #[error("INVALID_NFT: Invalid nft. Got ({received}), expected ({expected})")]
     InvalidNft { received: Addr, expected: Addr },
 
-    #[error("Asset {0} not found")]
+    #[error("ASSET_NOT_FOUND: Asset {0} not found")]
     AssetNotFound(AssetId),
 
-    #[error("Asset has expired")]
+    #[error("EXPIRED: Asset has expired")]
     Expired {},
 
-    #[error("Existing entitlement must be cleared before registering a new one")]
+    #[error(
+        "HAS_ACTIVE_ENTITLEMENT: Existing entitlement must be cleared before registering a new one"
+    )]
     HasActiveEntitlement {},
 
-    #[error("The asset cannot be withdrawn with an active entitlement")]
+    #[error("WITHDRAWAL_FAILED: The asset cannot be withdrawn with an active entitlement")]
     WithdrawalFailed {},
 
-    #[error("Expiry has already expired")]
+    #[error("INVALID_EXPIRY: Expiry has already expired")]
     InvalidExpiry {},
 
-    #[error("Beneficial owner must be set to impose an entitlement")]
+    #[error("BENEFICIAL_OWNER_NOT_SET: Beneficial owner must be set to impose an entitlement")]
     BeneficialOwnerNotSet {},
 
-    #[error("Invalid address: {0}")]
+    #[error("INVALID_ADDRESS: Invalid address: {0}")]
     InvalidAddress(String),
 
-    #[error("Invalid chain prefix: {0}")]
+    #[error("INVALID_CHAIN_PREFIX: Invalid chain prefix: {0}")]
     InvalidChainPrefix(String),
+
+    #[error("AUCTION_NOT_STARTED: Settlement auction has not started yet")]
+    AuctionNotStarted {},
+
+    #[error("DENOM_NOT_ALLOWED: Denom not allowed")]
+    DenomNotAllowed {},
+
+    #[error("OPTION_ALREADY_SETTLED: Option {0} already settled")]
+    OptionAlreadySettled(OptionId),
+
+    #[error("OPTION_IS_EXPIRED: Option has expired")]
+    OptionIsExpired {},
+
+    #[error("ONLY_OPTION_OWNER: Only the option owner can do it, not {0}")]
+    OnlyOptionOwner(String),
+
+    #[error("INVALID_STRIKE_DEPOSIT: Attached funds must be equal to the strike")]
+    InvalidStrikeDeposit {},
+
+    #[error("ZERO_STRIKE: Strike must be greater than zero")]
+    ZeroStrike {},
+
+    #[error("OPTION_DURATION_TOO_SHORT: Option must expire more than {min} seconds from now")]
+    OptionDurationTooShort { min: u64 },
+
+    #[error("ASSET_MISMATCH: The option is written for another asset")]
+    AssetMismatch {},
+
+    #[error("VAULT_NOT_FROM_PROTOCOL: The vault was not created by the protocol")]
+    VaultNotFromProtocol {},
+
+    #[error("PROTOCOL_PAUSED: Protocol is paused")]
+    ProtocolPaused {},
+
+    #[error(
+        "EXPIRATION_NOT_TIME: Expiration must be a time, height based and never ending expirations are not supported"
+    )]
+    ExpirationNotTime {},
+
+    #[error(
+        "OPTION_DURATION_OUT_OF_RANGE: Option duration must be between {min} and {max} seconds"
+    )]
+    OptionDurationOutOfRange { min: u64, max: u64 },
+
+    #[error(
+        "AUCTION_START_OFFSET_TOO_LONG: The settlement auction must start less than the minimum option duration of {min} seconds before expiration"
+    )]
+    AuctionStartOffsetTooLong { min: u64 },
+
+    #[error("EXPIRATION_NOT_IN_SERIES: Expiration is not on the expiry series of the instrument")]
+    ExpirationNotInSeries {},
+
+    #[error("INVALID_MIGRATION: Cannot migrate from {contract} {version}")]
+    InvalidMigration { contract: String, version: String },
+
+    #[error("NFT_NOT_ALLOWED: The nft is not the underlying collection of the instrument")]
+    NftNotAllowed {},
+
+    #[error("NOT_OWNER_OR_OPERATOR: Only the nft owner or an approved operator can write an option on it")]
+    NotOwnerOrOperator {},
+
+    #[error("INSTRUMENT_NOT_APPROVED: The instrument must be approved or an operator of the nft")]
+    InstrumentNotApproved {},
+
+    #[error("VAULT_NOT_FOUND: No vault holds the asset {0}")]
+    VaultNotFound(AssetId),
+
+    #[error("ASSET_NOT_IN_VAULT: Asset {0} is not in the vault")]
+    AssetNotInVault(AssetId),
+
+    #[error("ONLY_BENEFICIAL_OWNER_OR_OPERATOR: Only the beneficial owner or the approved operator can do it")]
+    OnlyBeneficialOwnerOrOperator {},
+
+    #[error("INVALID_BATCH_SIZE: A batch must contain between 1 and {max} entries")]
+    InvalidBatchSize { max: u64 },
+
+    #[error("TOO_MANY_OPTION_IDS: At most {max} option ids can be queried at once")]
+    TooManyOptionIds { max: u32 },
+
+    #[error("INVALID_OPTION_ID: Invalid option id {0}")]
+    InvalidOptionId(String),
+
+    #[error("DUPLICATE_ASSET: Asset {0} appears more than once in the batch")]
+    DuplicateAsset(AssetId),
+
+    #[error(
+        "INSTRUMENT_NOT_OPERATOR: The instrument is not the entitlement operator of the asset"
+    )]
+    InstrumentNotOperator {},
+
+    #[error(
+        "ENTITLEMENT_EXPIRATION_MISMATCH: The entitlement expiration does not match the option"
+    )]
+    EntitlementExpirationMismatch {},
+
+    #[error("PREVIOUS_OPTION_NOT_SETTLED: The previous option on the asset must be settled")]
+    PreviousOptionNotSettled {},
+
+    #[error("SEALED_BID_AUCTION: The instrument uses sealed bid auctions, commit a bid instead")]
+    SealedBidAuction {},
+
+    #[error("OPEN_AUCTION: The instrument uses open auctions")]
+    OpenAuction {},
+
+    #[error("BID_IS_LOWER_STRIKE_PRICE: Bid must be at least the strike")]
+    BidIsLowerStrikePrice {},
+
+    #[error("BID_INCREMENT_TOO_SMALL: Bid must be at least {min_bid}")]
+    BidIncrementTooSmall { min_bid: Uint128 },
+
+    #[error("BID_ALREADY_COMMITTED: A bid was already committed")]
+    BidAlreadyCommitted {},
+
+    #[error("ZERO_DEPOSIT: Deposit is zero")]
+    ZeroDeposit {},
+
+    #[error("NO_COMMITTED_BID: No committed bid")]
+    NoCommittedBid {},
+
+    #[error("BID_ALREADY_REVEALED: Bid was already revealed")]
+    BidAlreadyRevealed {},
+
+    #[error("COMMITMENT_MISMATCH: Bid does not match the commitment")]
+    CommitmentMismatch {},
+
+    #[error("DEPOSIT_TOO_LOW: Deposit does not cover the bid")]
+    DepositTooLow {},
+
+    #[error("REVEAL_PERIOD_OVER: Reveal period is over")]
+    RevealPeriodOver {},
+
+    #[error("REVEAL_PERIOD_NOT_OVER: Reveal period is not over")]
+    RevealPeriodNotOver {},
+
+    #[error("WINNING_DEPOSIT_LOCKED: The winning deposit is paid out on settlement")]
+    WinningDepositLocked {},
+
+    #[error("NOT_AMERICAN_OPTION: Only american style options can be exercised")]
+    NotAmericanOption {},
+
+    #[error("WRITER_MUST_OWN_OPTION: The writer must own the option")]
+    WriterMustOwnOption {},
+
+    #[error("OPTION_HAS_BIDS: Option has bids")]
+    OptionHasBids {},
+
+    #[error("NO_WINNING_BIDDER: Option has no winning bidder")]
+    NoWinningBidder {},
+
+    #[error("OPTION_NOT_EXPIRED: Option {0} has not expired")]
+    OptionNotExpired(OptionId),
+
+    #[error("ZERO_CLAIM: Nothing to claim")]
+    ZeroClaim {},
+
+    #[error("NO_PENDING_REFUNDS: No pending refunds")]
+    NoPendingRefunds {},
+
+    #[error("NO_ACTIVE_ENTITLEMENT: The asset has no active entitlement")]
+    NoActiveEntitlement {},
+
+    #[error("ONLY_ENTITLED_OPERATOR: Only the operator of the active entitlement can do it")]
+    OnlyEntitledOperator {},
+
+    #[error("ONLY_BENEFICIAL_OWNER: Only the beneficial owner can do it")]
+    OnlyBeneficialOwner {},
+
+    #[error("RECEIVER_NOT_BENEFICIAL_OWNER: Only the beneficial owner can receive the asset")]
+    ReceiverNotBeneficialOwner {},
+
+    #[error("LISTING_NOT_FOUND: Option {0} is not listed")]
+    ListingNotFound(OptionId),
+
+    #[error("LISTING_EXPIRED: The listing has expired")]
+    ListingExpired {},
+
+    #[error("LISTING_NOT_EXPIRED: The listing has not expired")]
+    ListingNotExpired {},
+
+    #[error("LISTING_HAS_BIDS: The listing has bids")]
+    ListingHasBids {},
+
+    #[error("NOT_AUCTION_LISTING: The listing is not a premium auction")]
+    NotAuctionListing {},
+
+    #[error("SELLER_NOT_OWNER: The seller no longer holds the option")]
+    SellerNotOwner {},
+
+    #[error("ZERO_PREMIUM: Premium is zero")]
+    ZeroPremium {},
+
+    #[error("INVALID_PREMIUM: Premium must be {premium}")]
+    InvalidPremium { premium: Uint128 },
+
+    #[error("PREMIUM_TOO_LOW: Premium bid must be at least {min_premium}")]
+    PremiumTooLow { min_premium: Uint128 },
+
+    #[error("ZERO_BURN_BOUNTY: Burn bounty is zero")]
+    ZeroBurnBounty {},
 }
@@ -0,0 +1,272 @@
+//! Typed events emitted by the protocol contracts. Each struct is the schema of one event,
+//! the chain prefixes the event type with `wasm-` and adds the `_contract_address` attribute.
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Event, Uint128};
+use cw_utils::Expiration;
+
+use crate::{
+    denom::Denom,
+    types::{AssetId, ExerciseStyle, OptionId, TokenId},
+};
+
+/// Value of an event attribute. Unset and empty values are left out of the event,
+/// because the chain rejects attributes with an empty value.
+pub trait AttributeValue {
+    fn to_value(&self) -> Option<String>;
+}
+
+macro_rules! display_attribute_value {
+    ($($ty:ty),*) => {
+        $(
+            impl AttributeValue for $ty {
+                fn to_value(&self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+display_attribute_value!(Addr, Uint128, u64, bool, String, Denom, Expiration);
+
+impl AttributeValue for ExerciseStyle {
+    fn to_value(&self) -> Option<String> {
+        match self {
+            ExerciseStyle::European => Some("european".to_string()),
+            ExerciseStyle::American => Some("american".to_string()),
+        }
+    }
+}
+
+impl<T: AttributeValue> AttributeValue for Option<T> {
+    fn to_value(&self) -> Option<String> {
+        self.as_ref().and_then(T::to_value)
+    }
+}
+
+/// Declares the event structs, their event type and the conversion into an `Event`
+/// with one attribute per set field.
+macro_rules! events {
+    ($(
+        $(#[doc = $doc:literal])*
+        $name:ident($ty:literal) {
+            $($(#[doc = $field_doc:literal])* $field:ident: $field_ty:ty,)*
+        }
+    )*) => {
+        $(
+            $(#[doc = $doc])*
+            #[cw_serde]
+            pub struct $name {
+                $($(#[doc = $field_doc])* pub $field: $field_ty,)*
+            }
+
+            impl $name {
+                pub const TYPE: &'static str = $ty;
+            }
+
+            impl From<$name> for Event {
+                fn from(event: $name) -> Event {
+                    let attributes: Vec<(&str, Option<String>)> =
+                        vec![$((stringify!($field), event.$field.to_value()),)*];
+                    Event::new($ty).add_attributes(attributes.into_iter().filter_map(
+                        |(key, value)| value.filter(|value| !value.is_empty()).map(|value| (key, value)),
+                    ))
+                }
+            }
+        )*
+    };
+}
+
+events! {
+    /// A call option was written on an asset held by a vault
+    OptionMinted("option_minted") {
+        option_id: OptionId,
+        writer: Addr,
+        vault: Addr,
+        asset_id: AssetId,
+        strike: Uint128,
+        denom: Denom,
+        expiration: Expiration,
+        exercise_style: ExerciseStyle,
+    }
+
+    /// The writer changed the terms of an option without bids
+    OptionAmended("option_amended") {
+        option_id: OptionId,
+        strike: Uint128,
+        expiration: Expiration,
+    }
+
+    /// A new high bid in an open settlement auction
+    BidPlaced("option_bid_placed") {
+        option_id: OptionId,
+        bidder: Addr,
+        /// The full bid, including the strike when the writer bids on the spread
+        amount: Uint128,
+        denom: Denom,
+        auction_end: Expiration,
+    }
+
+    /// The bid of an outbid bidder was returned, or credited to their pending refunds if the send failed
+    BidRefunded("option_bid_refunded") {
+        option_id: OptionId,
+        bidder: Addr,
+        amount: Uint128,
+        denom: Denom,
+    }
+
+    /// The holder of an american option exercised it by paying the strike
+    OptionExercised("option_exercised") {
+        option_id: OptionId,
+        holder: Addr,
+        strike: Uint128,
+        denom: Denom,
+    }
+
+    /// The writer reclaimed the asset of an option they hold, the option is settled and its nft burned
+    AssetReclaimed("option_asset_reclaimed") {
+        option_id: OptionId,
+        writer: Addr,
+        /// Whether the asset was withdrawn from the vault
+        withdrawn: bool,
+    }
+
+    /// The settlement auction of an option was settled
+    OptionSettled("option_settled") {
+        option_id: OptionId,
+        bidder: Addr,
+        bid: Uint128,
+        denom: Denom,
+        writer_amount: Uint128,
+        holder_amount: Uint128,
+        protocol_fee: Uint128,
+        strike_fee: Uint128,
+        royalty_amount: Uint128,
+        /// The caller of the settlement, paid the keeper bounty out of the spread
+        keeper: Addr,
+        keeper_bounty: Uint128,
+        /// The holder amount is kept until the holder claims it
+        claimable: bool,
+    }
+
+    /// The nft of an option that expired without bids was burned
+    ExpiredOptionBurned("option_expired_burned") {
+        option_id: OptionId,
+        /// The caller of the burn, paid the burn bounty funded by the writer
+        keeper: Addr,
+        burn_bounty: Uint128,
+        /// Whether the asset was withdrawn from the vault to the writer
+        withdrawn: bool,
+    }
+
+    /// The writer funded the bounty paid for burning their option once expired without bids
+    BurnBountyFunded("option_burn_bounty_funded") {
+        option_id: OptionId,
+        writer: Addr,
+        amount: Uint128,
+        denom: Denom,
+        /// The total bounty of the option
+        burn_bounty: Uint128,
+    }
+
+    /// The holder claimed the proceeds of an option settled by another account
+    ProceedsClaimed("option_proceeds_claimed") {
+        option_id: OptionId,
+        holder: Addr,
+        amount: Uint128,
+        denom: Denom,
+    }
+
+    /// The holder listed an option for sale, the option nft stays with them until it is bought
+    OptionListed("option_listed") {
+        option_id: OptionId,
+        seller: Addr,
+        /// The fixed price, or the reserve of a premium auction
+        premium: Uint128,
+        denom: Denom,
+        expires: Expiration,
+        auction: bool,
+    }
+
+    /// A new best bid in the premium auction of a listed option
+    ListingBidPlaced("option_listing_bid_placed") {
+        option_id: OptionId,
+        bidder: Addr,
+        amount: Uint128,
+        denom: Denom,
+    }
+
+    /// A bid in a premium auction was returned, or credited to the pending refunds of the bidder
+    ListingBidRefunded("option_listing_bid_refunded") {
+        option_id: OptionId,
+        bidder: Addr,
+        amount: Uint128,
+        denom: Denom,
+    }
+
+    /// A listed option was sold, the buyer paid the premium to the seller and received the option nft
+    OptionSold("option_sold") {
+        option_id: OptionId,
+        seller: Addr,
+        buyer: Addr,
+        premium: Uint128,
+        denom: Denom,
+    }
+
+    /// A listing was cancelled by the seller, or because the option settled
+    ListingCancelled("option_listing_cancelled") {
+        option_id: OptionId,
+        seller: Addr,
+    }
+
+    /// An asset was deposited into a vault
+    AssetDeposited("vault_asset_deposited") {
+        asset_id: AssetId,
+        beneficial_owner: Addr,
+    }
+
+    /// An asset left a vault
+    AssetWithdrawn("vault_asset_withdrawn") {
+        asset_id: AssetId,
+        receiver: Addr,
+    }
+
+    /// The beneficial owner of an asset changed
+    BeneficialOwnerSet("vault_beneficial_owner_set") {
+        asset_id: AssetId,
+        beneficial_owner: Option<Addr>,
+    }
+
+    /// An entitlement was imposed or granted on an asset
+    EntitlementSet("vault_entitlement_set") {
+        asset_id: AssetId,
+        beneficial_owner: Option<Addr>,
+        operator: Option<Addr>,
+        expiry: Option<Expiration>,
+    }
+
+    /// The operator released its entitlement on an asset
+    EntitlementCleared("vault_entitlement_cleared") {
+        asset_id: AssetId,
+        operator: Addr,
+    }
+
+    /// A vault was created by the vault factory, the `nft_id` attribute is only set for solo vaults
+    VaultCreated("vault_created") {
+        nft_addr: Addr,
+        nft_id: Option<TokenId>,
+        vault_addr: Addr,
+    }
+
+    /// A call instrument was created by the call factory
+    CallInstrumentCreated("call_instrument_created") {
+        nft_addr: Addr,
+        instrument_addr: Addr,
+    }
+
+    /// A put instrument was created by the call factory
+    PutInstrumentCreated("put_instrument_created") {
+        nft_addr: Addr,
+        instrument_addr: Addr,
+    }
+}
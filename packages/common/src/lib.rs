@@ -1,6 +1,8 @@
 pub mod addr;
 pub mod denom;
 pub mod errors;
+pub mod events;
+pub mod migrate;
 pub mod msg;
 pub mod nft;
 pub mod types;
@@ -0,0 +1,38 @@
+//! Version checks shared by the migrate entry points of the contracts.
+use cosmwasm_std::{ensure, StdError, StdResult, Storage};
+use semver::Version;
+
+use crate::errors::ContractError;
+
+/// Checks that the stored contract is the same contract at the same or an older version,
+/// then stores the new version.
+/// Returns the version the contract is migrated from, used to select the storage migrations.
+pub fn set_migrated_version(
+    storage: &mut dyn Storage,
+    contract_name: &str,
+    contract_version: &str,
+) -> Result<Version, ContractError> {
+    let stored = cw2::get_contract_version(storage)?;
+    let previous = parse_version(&stored.version)?;
+    let new = parse_version(contract_version)?;
+
+    ensure!(
+        stored.contract == contract_name && previous <= new,
+        ContractError::InvalidMigration {
+            contract: stored.contract,
+            version: stored.version,
+        }
+    );
+
+    cw2::set_contract_version(storage, contract_name, contract_version)?;
+    Ok(previous)
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    version.parse().map_err(|err: semver::Error| {
+        StdError::generic_err(format!(
+            "parse_version - invalid version {}: {}",
+            version, err
+        ))
+    })
+}
@@ -1,7 +1,9 @@
 use std::marker::PhantomData;
 
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    to_binary, Addr, Empty, MessageInfo, QuerierWrapper, StdError, StdResult, SubMsg, WasmMsg,
+    to_binary, Addr, Binary, Empty, MessageInfo, QuerierWrapper, StdError, StdResult, SubMsg,
+    Uint128, WasmMsg,
 };
 use cw721::{Approval, ApprovalResponse, ApprovalsResponse, Cw721ExecuteMsg, OwnerOfResponse};
 use cw721_base::helpers::Cw721Contract;
@@ -22,6 +24,28 @@ pub fn transfer_nft(collection: &Addr, token_id: &str, recipient: &Addr) -> StdR
     Ok(SubMsg::new(exec_cw721_transfer))
 }
 
+/// Invoke `send_nft` to build a `SubMsg` to send an NFT to a contract along with a message.
+pub fn send_nft(
+    collection: &Addr,
+    token_id: &str,
+    contract: &Addr,
+    msg: Binary,
+) -> StdResult<SubMsg> {
+    let cw721_send_msg = Cw721ExecuteMsg::SendNft {
+        contract: contract.to_string(),
+        token_id: token_id.to_string(),
+        msg,
+    };
+
+    let exec_cw721_send = WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_binary(&cw721_send_msg)?,
+        funds: vec![],
+    };
+
+    Ok(SubMsg::new(exec_cw721_send))
+}
+
 /// Invoke `owner_of` to get the owner of an NFT.
 pub fn owner_of(
     querier: &QuerierWrapper,
@@ -112,3 +136,38 @@ pub fn operator<T: Into<String>>(
             .query(querier, req)?;
     Ok(res.approval)
 }
+
+/// Royalty query of the cw2981 standard, wrapped into the cw721 `Extension` query.
+#[cw_serde]
+pub enum Cw2981QueryMsg {
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    pub royalty_amount: Uint128,
+}
+
+/// Invoke `royalty_info` to get the cw2981 royalty owed on a sale of the NFT.
+/// Returns None if the collection doesn't implement cw2981 or no royalty is owed.
+pub fn royalty_info(
+    querier: &QuerierWrapper,
+    collection: &Addr,
+    token_id: &str,
+    sale_price: Uint128,
+) -> Option<RoyaltiesInfoResponse> {
+    let req = cw721_base::QueryMsg::Extension {
+        msg: Cw2981QueryMsg::RoyaltyInfo {
+            token_id: token_id.to_string(),
+            sale_price,
+        },
+    };
+    querier
+        .query_wasm_smart::<RoyaltiesInfoResponse>(collection, &req)
+        .ok()
+        .filter(|royalty| !royalty.address.is_empty() && !royalty.royalty_amount.is_zero())
+}
@@ -1,4 +1,8 @@
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{ensure, BlockInfo, Timestamp};
+use cw_utils::Expiration;
+
+use crate::errors::ContractError;
 
 // TODO what type to use for identificators?
 pub type TokenId = String;
@@ -13,8 +17,153 @@ pub enum OptionType {
     PUT,
 }
 
+/// When the holder of a call option is able to exercise it.
+#[cw_serde]
+#[derive(Default)]
+pub enum ExerciseStyle {
+    /// The option is only settled through the settlement auction at expiration
+    #[default]
+    European,
+    /// The holder can also exercise the option at any time before expiration by paying the strike
+    American,
+}
+
+/// Bounds in seconds of the time between writing an option and its expiration.
 #[cw_serde]
 pub struct ExpiryRange {
     pub min: u64,
     pub max: u64,
 }
+
+impl ExpiryRange {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        ensure!(self.min <= self.max, ContractError::InvalidConfig {});
+        Ok(())
+    }
+
+    /// Checks that an option written at the given block expires within the range.
+    /// Only time based expirations are accepted, settlement auctions and entitlements are timed in seconds.
+    pub fn check(&self, expiration: &Expiration, block: &BlockInfo) -> Result<(), ContractError> {
+        let Expiration::AtTime(time) = expiration else {
+            return Err(ContractError::ExpirationNotTime {});
+        };
+        ensure!(
+            *time > block.time.plus_seconds(self.min) && *time <= block.time.plus_seconds(self.max),
+            ContractError::OptionDurationOutOfRange {
+                min: self.min,
+                max: self.max,
+            }
+        );
+        Ok(())
+    }
+}
+
+const HOUR: u64 = 3_600;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Standard grid of expirations new options must be written on, so liquidity concentrates in a few series.
+/// Times are UTC.
+#[cw_serde]
+pub enum ExpirySeries {
+    /// Every week on `weekday` (0 is Monday) at `hour`
+    Weekly { weekday: u8, hour: u8 },
+    /// Every month on `day` (1 to 28) at `hour`
+    Monthly { day: u8, hour: u8 },
+}
+
+impl ExpirySeries {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let valid = match *self {
+            ExpirySeries::Weekly { weekday, hour } => weekday < 7 && hour < 24,
+            ExpirySeries::Monthly { day, hour } => (1..=28).contains(&day) && hour < 24,
+        };
+        ensure!(valid, ContractError::InvalidConfig {});
+        Ok(())
+    }
+
+    /// Checks that an expiration is on the grid.
+    pub fn check(&self, expiration: &Expiration) -> Result<(), ContractError> {
+        let Expiration::AtTime(time) = expiration else {
+            return Err(ContractError::ExpirationNotTime {});
+        };
+        ensure!(
+            self.contains(*time),
+            ContractError::ExpirationNotInSeries {}
+        );
+        Ok(())
+    }
+
+    pub fn contains(&self, time: Timestamp) -> bool {
+        let seconds = time.seconds();
+        match *self {
+            ExpirySeries::Weekly { weekday, hour } => {
+                let first = weekly_offset(weekday, hour);
+                seconds >= first && (seconds - first).is_multiple_of(WEEK)
+            }
+            ExpirySeries::Monthly { day, hour } => {
+                let (_, _, d) = civil_from_days(seconds / DAY);
+                d == day as u64 && seconds % DAY == hour as u64 * HOUR
+            }
+        }
+    }
+
+    /// Returns the first expiration of the grid strictly after `time`.
+    pub fn next_after(&self, time: Timestamp) -> Timestamp {
+        let seconds = time.seconds();
+        let next = match *self {
+            ExpirySeries::Weekly { weekday, hour } => {
+                let first = weekly_offset(weekday, hour);
+                if seconds < first {
+                    first
+                } else {
+                    first + ((seconds - first) / WEEK + 1) * WEEK
+                }
+            }
+            ExpirySeries::Monthly { day, hour } => {
+                let (year, month, _) = civil_from_days(seconds / DAY);
+                let at = |year: u64, month: u64| {
+                    days_from_civil(year, month, day as u64) * DAY + hour as u64 * HOUR
+                };
+                let candidate = at(year, month);
+                if candidate > seconds {
+                    candidate
+                } else if month == 12 {
+                    at(year + 1, 1)
+                } else {
+                    at(year, month + 1)
+                }
+            }
+        };
+        Timestamp::from_seconds(next)
+    }
+}
+
+/// Seconds from the unix epoch, a Thursday, to the first weekly expiration.
+fn weekly_offset(weekday: u8, hour: u8) -> u64 {
+    ((weekday as u64 + 4) % 7) * DAY + hour as u64 * HOUR
+}
+
+/// Days since the unix epoch of a proleptic gregorian date, see http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Date (year, month, day) of a number of days since the unix epoch.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as u64;
+    (year, month, day)
+}
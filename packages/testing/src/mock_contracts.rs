@@ -1,8 +1,11 @@
-use cosmwasm_std::{to_binary, Addr, Coin, Empty, Uint128};
+use cosmwasm_std::{from_binary, to_binary, Addr, Coin, Empty, Uint128};
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 use cw_utils::Expiration;
 
-use call::QueryMsg as CallInstrumentQueryMsg;
+use call::{
+    msg::{MintBatchEntry, MintBatchResponse},
+    QueryMsg as CallInstrumentQueryMsg,
+};
 use common::types::{AssetId, OptionId, TokenId};
 use cw721_base::QueryMsg::Extension as Cw721Extension;
 use vault::msg::{CurrentEntitlementOperatorResponse, SetEntitlement};
@@ -31,7 +34,8 @@ impl Protocol {
             protocol::contract::execute,
             protocol::contract::instantiate,
             protocol::contract::query,
-        );
+        )
+        .with_migrate(protocol::contract::migrate);
         Box::new(contract)
     }
 
@@ -56,6 +60,27 @@ impl Protocol {
             )
             .unwrap();
     }
+
+    pub fn set_protocol_fee(
+        &self,
+        env: &mut MockEnv,
+        protocol_fee_bps: u64,
+        strike_fee_bps: u64,
+        fee_recipient: Option<String>,
+    ) {
+        env.app
+            .execute_contract(
+                env.admin.clone(),
+                self.contract_addr.clone(),
+                &protocol::ExecuteMsg::SetProtocolFee {
+                    protocol_fee_bps,
+                    strike_fee_bps,
+                    fee_recipient,
+                },
+                &[],
+            )
+            .unwrap();
+    }
 }
 
 /*
@@ -74,7 +99,8 @@ impl VaultFactory {
             vault_factory::contract::instantiate,
             vault_factory::contract::query,
         )
-        .with_reply(vault_factory::contract::reply);
+        .with_reply(vault_factory::contract::reply)
+        .with_migrate(vault_factory::contract::migrate);
         Box::new(contract)
     }
 
@@ -163,7 +189,8 @@ impl Vault {
             vault::contract::execute,
             vault::contract::instantiate,
             vault::contract::query,
-        );
+        )
+        .with_migrate(vault::contract::migrate);
         Box::new(contract)
     }
 
@@ -259,7 +286,8 @@ impl CallFactory {
             call_factory::contract::instantiate,
             call_factory::contract::query,
         )
-        .with_reply(call_factory::contract::reply);
+        .with_reply(call_factory::contract::reply)
+        .with_migrate(call_factory::contract::migrate);
         Box::new(contract)
     }
 
@@ -270,6 +298,11 @@ impl CallFactory {
                 self.contract_addr.clone(),
                 &call_factory::ExecuteMsg::MakeCallInstrument {
                     nft_addr: nft_addr.into(),
+                    royalties_enabled: None,
+                    auction_mode: None,
+                    anti_snipe: None,
+                    expiry_series: None,
+                    keeper_bounty: None,
                 },
                 &[],
             )
@@ -287,6 +320,31 @@ impl CallFactory {
             )
             .unwrap()
     }
+
+    pub fn make_put_instrument(&self, env: &mut MockEnv, nft_addr: &str) {
+        env.app
+            .execute_contract(
+                env.admin.clone(),
+                self.contract_addr.clone(),
+                &call_factory::ExecuteMsg::MakePutInstrument {
+                    nft_addr: nft_addr.into(),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    pub fn query_put_instrument(&self, env: &MockEnv, nft_addr: &str) -> Option<Addr> {
+        env.app
+            .wrap()
+            .query_wasm_smart(
+                &self.contract_addr,
+                &call_factory::QueryMsg::GetPutInstrument {
+                    nft_addr: nft_addr.into(),
+                },
+            )
+            .unwrap()
+    }
 }
 
 /*
@@ -304,7 +362,9 @@ impl CallInstrument {
             call::contract::execute,
             call::contract::instantiate,
             call::contract::query,
-        );
+        )
+        .with_reply(call::contract::reply)
+        .with_migrate(call::contract::migrate);
         Box::new(contract)
     }
 
@@ -321,12 +381,13 @@ impl CallInstrument {
             .execute_contract(
                 env.writer.clone(),
                 self.contract_addr.clone(),
-                &cw721_base::ExecuteMsg::<Empty, call::ExecuteMsg>::Extension {
+                &cw721_base::ExecuteMsg::<call::msg::OptionMetadata, call::ExecuteMsg>::Extension {
                     msg: call::ExecuteMsg::MintWithNFT {
                         nft_addr: nft_addr.to_owned(),
                         nft_id,
                         strike,
                         expiration,
+                        exercise_style: None,
                     },
                 },
                 &[],
@@ -344,12 +405,35 @@ impl CallInstrument {
             .unwrap()
     }
 
+    pub fn mint_batch_with_nft(
+        &self,
+        env: &mut MockEnv,
+        nft_addr: &str,
+        entries: Vec<MintBatchEntry>,
+    ) -> anyhow::Result<Vec<OptionId>> {
+        let resp = env.app.execute_contract(
+            env.writer.clone(),
+            self.contract_addr.clone(),
+            &cw721_base::ExecuteMsg::<call::msg::OptionMetadata, call::ExecuteMsg>::Extension {
+                msg: call::ExecuteMsg::MintBatchWithNFT {
+                    nft_addr: nft_addr.to_owned(),
+                    entries,
+                    exercise_style: None,
+                },
+            },
+            &[],
+        )?;
+
+        let batch: MintBatchResponse = from_binary(&resp.data.unwrap())?;
+        Ok(batch.option_ids)
+    }
+
     pub fn bid(&self, env: &mut MockEnv, option_id: OptionId, coin: Coin) {
         env.app
             .execute_contract(
                 env.bidder.clone(),
                 self.contract_addr.clone(),
-                &cw721_base::ExecuteMsg::<Empty, call::ExecuteMsg>::Extension {
+                &cw721_base::ExecuteMsg::<call::msg::OptionMetadata, call::ExecuteMsg>::Extension {
                     msg: call::ExecuteMsg::Bid { option_id },
                 },
                 &[coin],
@@ -450,6 +534,85 @@ impl CallInstrument {
     }
 }
 
+/*
+    Put instrument contract mock
+*/
+
+#[derive(Clone, Debug)]
+pub struct PutInstrument {
+    pub contract_addr: Addr,
+}
+
+impl PutInstrument {
+    pub fn mock_contract() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            put::contract::execute,
+            put::contract::instantiate,
+            put::contract::query,
+        )
+        .with_migrate(put::contract::migrate);
+        Box::new(contract)
+    }
+
+    pub fn mint_put(
+        &self,
+        env: &mut MockEnv,
+        asset_id: Option<AssetId>,
+        strike: Coin,
+        expiration: Expiration,
+    ) -> OptionId {
+        let resp = env
+            .app
+            .execute_contract(
+                env.writer.clone(),
+                self.contract_addr.clone(),
+                &cw721_base::ExecuteMsg::<Empty, put::ExecuteMsg>::Extension {
+                    msg: put::ExecuteMsg::MintPut {
+                        asset_id,
+                        strike: strike.amount,
+                        expiration,
+                    },
+                },
+                &[strike],
+            )
+            .unwrap();
+
+        // find option_id in events
+        resp.events[1]
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "option_id")
+            .unwrap()
+            .value
+            .parse::<u64>()
+            .unwrap()
+    }
+
+    pub fn query_get_strike_price(&self, env: &MockEnv, option_id: OptionId) -> Uint128 {
+        env.app
+            .wrap()
+            .query_wasm_smart(
+                &self.contract_addr,
+                &Cw721Extension {
+                    msg: put::QueryMsg::GetStrikePrice { option_id },
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_get_writer(&self, env: &MockEnv, option_id: OptionId) -> Addr {
+        env.app
+            .wrap()
+            .query_wasm_smart(
+                &self.contract_addr,
+                &Cw721Extension {
+                    msg: put::QueryMsg::GetWriter { option_id },
+                },
+            )
+            .unwrap()
+    }
+}
+
 // Cw721 NFT contract mock
 
 #[derive(Clone, Debug)]
@@ -2,10 +2,13 @@
 
 use std::mem::take;
 
-use cosmwasm_std::{Addr, BlockInfo, Coin, StdResult, Uint128};
+use common::{denom::Denom, types::ExpiryRange};
+use cosmwasm_std::{Addr, BlockInfo, Coin, StdResult};
 use cw_multi_test::{App, BankSudo, BasicApp, Executor, SudoMsg};
 
-use crate::mock_contracts::{CallFactory, CallInstrument, Cw721Nft, Protocol, Vault, VaultFactory};
+use crate::mock_contracts::{
+    CallFactory, CallInstrument, Cw721Nft, Protocol, PutInstrument, Vault, VaultFactory,
+};
 
 pub const WRITER: &str = "writer";
 pub const ADMIN: &str = "admin";
@@ -18,9 +21,14 @@ const UNDERLYING_NFT_NAME: &str = "BORED APE YACHT CLUB";
 const CALL_OPTION_NFT_SYMBOL: &str = "CALOPT";
 const CALL_OPTION_NFT_NAME: &str = "call option nft";
 
-const MINIMUM_OPTION_DURATION: u64 = 1;
+const PUT_OPTION_NFT_SYMBOL: &str = "PUTOPT";
+const PUT_OPTION_NFT_NAME: &str = "put option nft";
+
+const MINIMUM_OPTION_DURATION: u64 = 3600;
+const MAXIMUM_OPTION_DURATION: u64 = 365 * 86400;
 const ALLOWED_DENOM: &str = "ATOM";
-const MIN_BID_INC_BIPS: Uint128 = Uint128::new(1);
+const MIN_BID_INCREMENT_BPS: u64 = 1;
+const SETTLEMENT_AUCTION_START_OFFSET: u64 = 1800;
 
 pub struct MockEnv {
     pub app: App,
@@ -121,8 +129,13 @@ impl MockEnvBuilder {
 
         // deploy call factory contract - contract3
         let call_code_id = self.app.store_code(CallInstrument::mock_contract());
-        let call_factory_addr =
-            self.deploy_call_factory(call_code_id, &protocol_addr, &vault_factory_addr);
+        let put_code_id = self.app.store_code(PutInstrument::mock_contract());
+        let call_factory_addr = self.deploy_call_factory(
+            call_code_id,
+            put_code_id,
+            &protocol_addr,
+            &vault_factory_addr,
+        );
 
         // update factories addresses in protocol contract
         self.set_protocol_factory(
@@ -220,6 +233,7 @@ impl MockEnvBuilder {
     fn deploy_call_factory(
         &mut self,
         call_code_id: u64,
+        put_code_id: u64,
         protocol_addr: &Addr,
         vault_factory_addr: &Addr,
     ) -> Addr {
@@ -235,10 +249,17 @@ impl MockEnvBuilder {
                     owner: None, // owner is sender
                     nft_symbol: CALL_OPTION_NFT_SYMBOL.to_string(),
                     nft_name: CALL_OPTION_NFT_NAME.to_string(),
-                    default_minimum_option_duration: MINIMUM_OPTION_DURATION,
-                    default_allowed_denom: ALLOWED_DENOM.to_string(),
-                    default_min_bid_inc_bips: MIN_BID_INC_BIPS,
+                    default_expiry_range: ExpiryRange {
+                        min: MINIMUM_OPTION_DURATION,
+                        max: MAXIMUM_OPTION_DURATION,
+                    },
+                    default_allowed_denom: Denom::from(ALLOWED_DENOM),
+                    default_min_bid_increment_bps: MIN_BID_INCREMENT_BPS,
+                    default_settlement_auction_start_offset: SETTLEMENT_AUCTION_START_OFFSET,
                     vault_factory_addr: vault_factory_addr.to_string(),
+                    put_code_id,
+                    put_nft_symbol: PUT_OPTION_NFT_SYMBOL.to_string(),
+                    put_nft_name: PUT_OPTION_NFT_NAME.to_string(),
                 },
                 &[],
                 "call-factory",